@@ -0,0 +1,329 @@
+//! Contains the abstract syntax tree produced by the parser.
+pub mod ast {
+
+    /// Root node of a query.
+    /// Holds the name of the main table, followed by the pipeline of statements applied to it.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Program {
+        pub table: String,
+        pub statements: Vec<Statement>,
+    }
+
+    /// A single `| <OPERATOR>` stage of the pipeline.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Statement {
+        /// `| READ <format>[(<option> = <value>, ...)] ["<path>"]`
+        /// Without a path, the file is read from the path bound to the table.
+        /// For `| READ sql("<table>", <connection>)`, the path holds the name of the database table.
+        Read {
+            format: FileFormat,
+            options: Vec<FormatOption>,
+            path: Option<String>,
+        },
+        /// `| WHERE <condition>`
+        Where { condition: Expression },
+        /// `| EXTEND <column> = <expression>`
+        Extend { column: Column, value: Expression },
+        /// `| PROJECT <column>[ = <expression>], ...`
+        /// Keeps only the listed columns, in order. Columns given an expression are computed first.
+        Project { columns: Vec<ProjectColumn> },
+        /// `| PROJECT-AWAY <column>, ...`
+        ProjectAway { columns: Vec<Column> },
+        /// `| PROJECT-RENAME <new column> = <column>, ...`
+        ProjectRename { renames: Vec<Rename> },
+        /// `| SORT BY <column> [asc | desc] [nulls first | nulls last], ...`
+        Sort { keys: Vec<SortKey> },
+        /// `| DISTINCT <column>, ...` or `| DISTINCT *`
+        /// Keeps the unique combinations of the columns. No columns stands for all of them.
+        Distinct { columns: Vec<Column> },
+        /// `| SUMMARIZE [<column> =] <aggregate>(<arguments>), ... [BY <column>, ...]`
+        /// Without BY, the table is aggregated into a single row.
+        Summarize {
+            aggregations: Vec<Aggregation>,
+            by: Vec<Column>,
+        },
+        /// `| COUNT`
+        /// Replaces the table with a single `Count` row holding its number of rows.
+        Count,
+        /// `| GETSCHEMA`
+        /// Replaces the table with the name, position and type of each of its columns.
+        GetSchema,
+        /// `| TAKE <count>` or `| LIMIT <count>`
+        Take { count: u32 },
+        /// `| TOP <count> BY <column> [asc | desc] [nulls first | nulls last], ...`
+        Top { count: u32, keys: Vec<SortKey> },
+        /// `| SAMPLE <count> [seed = <seed>]`
+        Sample { count: u32, seed: Option<u32> },
+        /// `| JOIN [kind = <kind>] (<query>) ON <column> | $left.<column> == $right.<column>, ...`
+        /// The query in brackets builds the right table of the join.
+        Join {
+            kind: JoinKind,
+            right: Program,
+            on: Vec<JoinKey>,
+        },
+        /// `| WRITE <format>[(<option> = <value>, ...)] "<path>"`
+        /// For `| WRITE sql("<table>", <connection>)`, the path holds the name of the database table.
+        Write {
+            format: FileFormat,
+            options: Vec<FormatOption>,
+            path: String,
+        },
+    }
+
+    /// File formats understood by the READ statement.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum FileFormat {
+        Csv,
+        Excel,
+        Parquet,
+        Json,
+        /// Newline-delimited JSON.
+        JsonLines,
+        Feather,
+        /// A database table, read through the connection held by the named Python variable.
+        Sql {
+            connection: String,
+        },
+    }
+
+    impl FileFormat {
+        /// Names of all formats, as written in queries.
+        pub const NAMES: &'static [&'static str] =
+            &["csv", "excel", "parquet", "json", "jsonl", "feather", "sql"];
+
+        /// Name of the format, as written in queries.
+        pub fn name(&self) -> &'static str {
+            match self {
+                FileFormat::Csv => "csv",
+                FileFormat::Excel => "excel",
+                FileFormat::Parquet => "parquet",
+                FileFormat::Json => "json",
+                FileFormat::JsonLines => "jsonl",
+                FileFormat::Feather => "feather",
+                FileFormat::Sql { .. } => "sql",
+            }
+        }
+
+        /// Options accepted when reading a file of this format.
+        pub fn read_options(&self) -> &'static [&'static str] {
+            match self {
+                FileFormat::Csv => &["sep", "header", "encoding", "skiprows", "decimal"],
+                FileFormat::Excel => &["sheet", "header", "skiprows"],
+                FileFormat::Parquet => &["engine"],
+                FileFormat::Json => &["orient", "encoding"],
+                FileFormat::JsonLines => &["encoding"],
+                FileFormat::Feather => &[],
+                FileFormat::Sql { .. } => &["schema", "index_col"],
+            }
+        }
+
+        /// Options accepted when writing a file of this format.
+        pub fn write_options(&self) -> &'static [&'static str] {
+            match self {
+                FileFormat::Csv => &["sep", "header", "encoding", "decimal", "index"],
+                FileFormat::Excel => &["sheet", "header", "index"],
+                FileFormat::Parquet => &["engine", "compression", "index"],
+                FileFormat::Json => &["orient", "indent"],
+                FileFormat::JsonLines => &[],
+                FileFormat::Feather => &[],
+                FileFormat::Sql { .. } => &["schema", "if_exists", "index"],
+            }
+        }
+    }
+
+    /// A `<name> = <value>` option passed to a file format, e.g. `sep = ";"`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct FormatOption {
+        pub name: String,
+        pub value: Literal,
+    }
+
+    /// A column kept by PROJECT, optionally computed from an expression.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ProjectColumn {
+        pub column: Column,
+        pub value: Option<Expression>,
+    }
+
+    /// A `<new> = <old>` column rename.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Rename {
+        pub new: Column,
+        pub old: Column,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum JoinKind {
+        Inner,
+        LeftOuter,
+        RightOuter,
+        FullOuter,
+        /// Rows of the left table without a match in the right table.
+        LeftAnti,
+        /// Rows of the left table with a match in the right table.
+        LeftSemi,
+    }
+
+    impl JoinKind {
+        /// Names of all join kinds, as written in queries.
+        pub const NAMES: &'static [&'static str] = &[
+            "inner",
+            "leftouter",
+            "rightouter",
+            "fullouter",
+            "leftanti",
+            "leftsemi",
+        ];
+    }
+
+    /// A pair of columns which must be equal for rows to be joined.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct JoinKey {
+        pub left: Column,
+        pub right: Column,
+    }
+
+    /// A column to sort by. Columns are sorted in descending order unless stated otherwise.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SortKey {
+        pub column: Column,
+        pub ascending: bool,
+        pub nulls: Option<NullsPosition>,
+    }
+
+    /// Where null values are placed by a sort.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum NullsPosition {
+        First,
+        Last,
+    }
+
+    /// An aggregate computed by SUMMARIZE, stored in the `name` column.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Aggregation {
+        pub name: Column,
+        pub function: AggregateFunction,
+        /// Only `count()` has no argument.
+        pub argument: Option<Expression>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum AggregateFunction {
+        Count,
+        Sum,
+        Avg,
+        Min,
+        Max,
+        Median,
+        Stdev,
+        Variance,
+        /// Number of distinct values.
+        DCount,
+        /// Number of rows for which the condition holds.
+        CountIf,
+        /// Holds the percentile, between 0 and 100.
+        Percentile(f64),
+    }
+
+    impl AggregateFunction {
+        /// Names of all aggregate functions, as written in queries.
+        pub const NAMES: &'static [&'static str] = &[
+            "count",
+            "sum",
+            "avg",
+            "mean",
+            "min",
+            "max",
+            "median",
+            "stdev",
+            "variance",
+            "dcount",
+            "countif",
+            "percentile",
+        ];
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Expression {
+        Column(Column),
+        Literal(Literal),
+        Unary {
+            operator: UnaryOperator,
+            operand: Box<Expression>,
+        },
+        Binary {
+            operator: BinaryOperator,
+            left: Box<Expression>,
+            right: Box<Expression>,
+        },
+        /// `isnotnull(<column>)`
+        IsNotNull(Column),
+        /// `isnull(<column>)`
+        IsNull(Column),
+    }
+
+    /// Reference to a column of the main table.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Column {
+        pub name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Literal {
+        Integer(i64),
+        Float(f64),
+        String(String),
+        Boolean(bool),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UnaryOperator {
+        Plus,
+        Minus,
+        Not,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BinaryOperator {
+        Add,
+        Subtract,
+        Multiply,
+        Divide,
+        GreaterThan,
+        GreaterThanEqualsTo,
+        LessThan,
+        LessThanEqualsTo,
+        EqualsTo,
+        NotEqualsTo,
+        And,
+        Or,
+    }
+
+    impl BinaryOperator {
+        /// Binding strength of the operator. Higher binds tighter.
+        pub fn precedence(&self) -> u8 {
+            match self {
+                BinaryOperator::Or => 1,
+                BinaryOperator::And => 2,
+                BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanEqualsTo
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessThanEqualsTo
+                | BinaryOperator::EqualsTo
+                | BinaryOperator::NotEqualsTo => 3,
+                BinaryOperator::Add | BinaryOperator::Subtract => 4,
+                BinaryOperator::Multiply | BinaryOperator::Divide => 5,
+            }
+        }
+
+        /// Returns true for the operators comparing two values.
+        pub fn is_comparison(&self) -> bool {
+            self.precedence() == 3
+        }
+
+        /// Returns true for the boolean AND / OR combinators.
+        pub fn is_logical(&self) -> bool {
+            matches!(self, BinaryOperator::And | BinaryOperator::Or)
+        }
+    }
+}
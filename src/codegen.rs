@@ -0,0 +1,849 @@
+//! Contains the code generator used for turning the parsed AST into Python dataframe code.
+pub mod codegen {
+    use std::collections::HashMap;
+
+    use crate::ast::ast::{
+        AggregateFunction, Aggregation, BinaryOperator, Column, Expression, FileFormat,
+        FormatOption, JoinKey, JoinKind, Literal, NullsPosition, Program, ProjectColumn, Rename,
+        SortKey, Statement, UnaryOperator,
+    };
+    use crate::errors::TranspileErr::TranspileErr;
+
+    /// Code generator struct.
+    /// Walks a `Program` and emits the equivalent pandas code.
+    pub struct PythonCodeGen {
+        main_table_name: String,
+        python_output: String,
+        bindings: HashMap<String, String>,
+        placeholder: Option<String>,
+    }
+
+    impl Default for PythonCodeGen {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl PythonCodeGen {
+        pub fn new() -> Self {
+            PythonCodeGen {
+                main_table_name: "".to_string(),
+                python_output: "".to_string(),
+                bindings: HashMap::new(),
+                placeholder: None,
+            }
+        }
+
+        /// Binds a table to the path of the file it is read from.
+        /// Used by READ statements which do not specify a path themselves.
+        pub fn bind(mut self, table: impl Into<String>, path: impl Into<String>) -> Self {
+            self.bindings.insert(table.into(), path.into());
+            self
+        }
+
+        /// Emits the placeholder, as is, in place of the path of tables which are neither read from a path nor bound.
+        /// Without a placeholder, such tables are an error.
+        pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+            self.placeholder = Some(placeholder.into());
+            self
+        }
+
+        /// Consumes the generator and returns the Python code for the input program.
+        pub fn generate(mut self, program: &Program) -> Result<String, TranspileErr> {
+            if self.placeholder.is_none() {
+                if let Some(table) = self.unbound_table(program) {
+                    return Err(TranspileErr::UnboundTable(table));
+                }
+            }
+            self.program(program);
+            Ok(self.python_output)
+        }
+
+        /// Returns the first table of the program, or of its joined queries, without an input path.
+        fn unbound_table(&self, program: &Program) -> Option<String> {
+            let reads_path = matches!(
+                program.statements.first(),
+                Some(Statement::Read { path: Some(_), .. })
+            );
+            if !reads_path && !self.bindings.contains_key(&program.table) {
+                return Some(program.table.clone());
+            }
+            program
+                .statements
+                .iter()
+                .find_map(|statement| match statement {
+                    Statement::Join { right, .. } => self.unbound_table(right),
+                    _ => None,
+                })
+        }
+
+        fn program(&mut self, program: &Program) {
+            self.python_output.push_str("import pandas as pd\n\n");
+            self.main_table_name.push_str(&program.table); // Indicating that this will be the main table.
+            self.query(program);
+        }
+
+        /// Emits the code building the table of the query, which must be the current main table.
+        fn query(&mut self, program: &Program) {
+            // The table variable initially holds the path of its file, unless the query starts by reading from an explicit path.
+            let source = match self.bindings.get(&program.table) {
+                Some(path) => Some(python_string(path)),
+                None => match program.statements.first() {
+                    Some(Statement::Read { path: Some(_), .. }) => None,
+                    _ => self.placeholder.clone(),
+                },
+            };
+            if let Some(source) = source {
+                let gen_code = format!("{} = {} \n", program.table, source);
+                self.python_output.push_str(&gen_code);
+            }
+
+            for statement in program.statements.iter() {
+                self.statement(statement);
+            }
+        }
+
+        fn statement(&mut self, statement: &Statement) {
+            match statement {
+                Statement::Read {
+                    format,
+                    options,
+                    path,
+                } => self.read_statement(format, options, path),
+                Statement::Where { condition } => self.where_statement(condition),
+                Statement::Extend { column, value } => self.extend_statement(column, value),
+                Statement::Project { columns } => self.project_statement(columns),
+                Statement::ProjectAway { columns } => self.project_away_statement(columns),
+                Statement::ProjectRename { renames } => self.project_rename_statement(renames),
+                Statement::Sort { keys } => self.sort_statement(keys),
+                Statement::Distinct { columns } => self.distinct_statement(columns),
+                Statement::Summarize { aggregations, by } => {
+                    self.summarize_statement(aggregations, by)
+                }
+                Statement::Count => self.count_statement(),
+                Statement::GetSchema => self.get_schema_statement(),
+                Statement::Take { count } => self.take_statement(*count),
+                Statement::Top { count, keys } => self.top_statement(*count, keys),
+                Statement::Sample { count, seed } => self.sample_statement(*count, *seed),
+                Statement::Join { kind, right, on } => self.join_statement(kind, right, on),
+                Statement::Write {
+                    format,
+                    options,
+                    path,
+                } => self.write_statement(format, options, path),
+            }
+        }
+
+        fn read_statement(
+            &mut self,
+            format: &FileFormat,
+            options: &[FormatOption],
+            path: &Option<String>,
+        ) {
+            let reader = match format {
+                FileFormat::Csv => "read_csv",
+                FileFormat::Excel => "read_excel",
+                FileFormat::Parquet => "read_parquet",
+                FileFormat::Json | FileFormat::JsonLines => "read_json",
+                FileFormat::Feather => "read_feather",
+                FileFormat::Sql { .. } => "read_sql_table",
+            };
+            let source = match path {
+                Some(path) => python_string(path),
+                None => self.main_table_name.clone(),
+            };
+            let code_gen = format!("{} = pd.{}({}", self.main_table_name, reader, source);
+            self.python_output.push_str(&code_gen);
+            match format {
+                FileFormat::JsonLines => self.python_output.push_str(", lines=True"),
+                FileFormat::Sql { connection } => {
+                    self.python_output.push_str(&format!(", {}", connection))
+                }
+                _ => {}
+            }
+            self.format_options(options);
+            self.python_output.push_str(") \n");
+        }
+
+        /// Emits format options as keyword arguments of the pandas reader / writer call.
+        fn format_options(&mut self, options: &[FormatOption]) {
+            for option in options.iter() {
+                let keyword = match option.name.as_str() {
+                    "sheet" => "sheet_name",
+                    name => name,
+                };
+                self.python_output.push_str(&format!(", {}=", keyword));
+                self.literal(&option.value);
+            }
+        }
+
+        fn write_statement(&mut self, format: &FileFormat, options: &[FormatOption], path: &str) {
+            // Feather files only store frames with a default index, which filtering does not preserve.
+            let writer = match format {
+                FileFormat::Csv => "to_csv",
+                FileFormat::Excel => "to_excel",
+                FileFormat::Parquet => "to_parquet",
+                FileFormat::Json | FileFormat::JsonLines => "to_json",
+                FileFormat::Feather => "reset_index(drop=True).to_feather",
+                FileFormat::Sql { .. } => "to_sql",
+            };
+            let code_gen = format!(
+                "{}.{}({}",
+                self.main_table_name,
+                writer,
+                python_string(path)
+            );
+            self.python_output.push_str(&code_gen);
+            match format {
+                FileFormat::JsonLines => self
+                    .python_output
+                    .push_str(", orient=\"records\", lines=True"),
+                FileFormat::Sql { connection } => {
+                    self.python_output.push_str(&format!(", {}", connection))
+                }
+                _ => {}
+            }
+            // The index is not part of the queried data, so it is left out unless asked for.
+            let writes_index = matches!(
+                format,
+                FileFormat::Csv | FileFormat::Excel | FileFormat::Parquet | FileFormat::Sql { .. }
+            );
+            if writes_index && !options.iter().any(|option| option.name == "index") {
+                self.python_output.push_str(", index=False");
+            }
+            self.format_options(options);
+            self.python_output.push_str(")\n");
+        }
+
+        fn where_statement(&mut self, condition: &Expression) {
+            self.python_output.push_str("cond = (");
+            self.expression(condition);
+            self.python_output.push_str(")\n");
+            let code_gen = format!(
+                "{} = {}[cond]\n",
+                self.main_table_name, self.main_table_name
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn extend_statement(&mut self, column: &Column, value: &Expression) {
+            self.column(column);
+            self.python_output.push_str(" = ");
+            self.expression(value);
+            self.python_output.push('\n');
+        }
+
+        fn project_statement(&mut self, columns: &[ProjectColumn]) {
+            // Every expression reads the input table, so the computed columns are assigned together.
+            let computed: Vec<&ProjectColumn> =
+                columns.iter().filter(|c| c.value.is_some()).collect();
+            if !computed.is_empty() {
+                let code_gen = format!(
+                    "{} = {}.assign(**{{",
+                    self.main_table_name, self.main_table_name
+                );
+                self.python_output.push_str(&code_gen);
+                for (i, project_column) in computed.iter().enumerate() {
+                    if i > 0 {
+                        self.python_output.push_str(", ");
+                    }
+                    self.python_output
+                        .push_str(&format!("{}: ", python_string(&project_column.column.name)));
+                    if let Some(value) = &project_column.value {
+                        self.expression(value);
+                    }
+                }
+                self.python_output.push_str("})\n");
+            }
+            let selected: Vec<Column> = columns.iter().map(|c| c.column.clone()).collect();
+            let code_gen = format!(
+                "{} = {}.loc[:,{}]\n",
+                self.main_table_name,
+                self.main_table_name,
+                column_names(&selected)
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn project_away_statement(&mut self, columns: &[Column]) {
+            let code_gen = format!(
+                "{} = {}.drop(columns={})\n",
+                self.main_table_name,
+                self.main_table_name,
+                column_names(columns)
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn project_rename_statement(&mut self, renames: &[Rename]) {
+            let mapping: Vec<String> = renames
+                .iter()
+                .map(|rename| {
+                    format!(
+                        "{}: {}",
+                        python_string(&rename.old.name),
+                        python_string(&rename.new.name)
+                    )
+                })
+                .collect();
+            let code_gen = format!(
+                "{} = {}.rename(columns={{{}}})\n",
+                self.main_table_name,
+                self.main_table_name,
+                mapping.join(", ")
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn sort_statement(&mut self, keys: &[SortKey]) {
+            let code_gen = format!("{} = {}", self.main_table_name, self.main_table_name);
+            self.python_output.push_str(&code_gen);
+            self.sort_values(keys);
+            self.python_output.push('\n');
+        }
+
+        fn distinct_statement(&mut self, columns: &[Column]) {
+            let code_gen = match columns.is_empty() {
+                true => format!(
+                    "{} = {}.drop_duplicates()\n",
+                    self.main_table_name, self.main_table_name
+                ),
+                false => format!(
+                    "{} = {}.drop_duplicates(subset={}).loc[:,{}]\n",
+                    self.main_table_name,
+                    self.main_table_name,
+                    column_names(columns),
+                    column_names(columns)
+                ),
+            };
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn summarize_statement(&mut self, aggregations: &[Aggregation], by: &[Column]) {
+            if by.is_empty() {
+                return self.summarize_all(aggregations);
+            }
+
+            // Named aggregations only take columns, so other arguments are computed into helper columns first.
+            let mut sources = Vec::new();
+            for (i, aggregation) in aggregations.iter().enumerate() {
+                let source = match &aggregation.argument {
+                    Some(Expression::Column(column)) => column.clone(),
+                    argument => {
+                        let helper = Column {
+                            name: format!("__summarize_{}", i),
+                        };
+                        let value = argument
+                            .clone()
+                            .unwrap_or(Expression::Literal(Literal::Integer(1)));
+                        self.extend_statement(&helper, &value);
+                        helper
+                    }
+                };
+                sources.push(source);
+            }
+
+            let named: Vec<String> = aggregations
+                .iter()
+                .zip(sources.iter())
+                .map(|(aggregation, source)| {
+                    let function = match aggregation.function {
+                        AggregateFunction::Count if aggregation.argument.is_none() => {
+                            python_string("size")
+                        }
+                        AggregateFunction::Percentile(percentile) => {
+                            format!("lambda s: s.quantile({})", quantile(percentile))
+                        }
+                        function => python_string(aggregate_method(function)),
+                    };
+                    format!(
+                        "{}: ({}, {})",
+                        python_string(&aggregation.name.name),
+                        python_string(&source.name),
+                        function
+                    )
+                })
+                .collect();
+            let code_gen = format!(
+                "{} = {}.groupby({}, dropna=False).agg(**{{{}}}).reset_index()\n",
+                self.main_table_name,
+                self.main_table_name,
+                column_names(by),
+                named.join(", ")
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        /// Aggregates the whole table into a single row.
+        fn summarize_all(&mut self, aggregations: &[Aggregation]) {
+            let code_gen = format!("{} = pd.DataFrame({{", self.main_table_name);
+            self.python_output.push_str(&code_gen);
+            for (i, aggregation) in aggregations.iter().enumerate() {
+                if i > 0 {
+                    self.python_output.push_str(", ");
+                }
+                self.python_output
+                    .push_str(&format!("{}: [", python_string(&aggregation.name.name)));
+                match &aggregation.argument {
+                    None => {
+                        let code_gen = format!("len({})", self.main_table_name);
+                        self.python_output.push_str(&code_gen);
+                    }
+                    Some(argument) => {
+                        match argument {
+                            Expression::Column(column) => self.column(column),
+                            argument => {
+                                self.python_output.push('(');
+                                self.expression(argument);
+                                self.python_output.push(')');
+                            }
+                        }
+                        let call = match aggregation.function {
+                            AggregateFunction::Percentile(percentile) => {
+                                format!(".quantile({})", quantile(percentile))
+                            }
+                            function => format!(".{}()", aggregate_method(function)),
+                        };
+                        self.python_output.push_str(&call);
+                    }
+                }
+                self.python_output.push(']');
+            }
+            self.python_output.push_str("})\n");
+        }
+
+        fn count_statement(&mut self) {
+            let code_gen = format!(
+                "{} = pd.DataFrame({{\"Count\": [len({})]}})\n",
+                self.main_table_name, self.main_table_name
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn get_schema_statement(&mut self) {
+            let code_gen = format!(
+                "{0} = pd.DataFrame({{\"ColumnName\": list({0}.columns), \"ColumnOrdinal\": range(len({0}.columns)), \"DataType\": {0}.dtypes.astype(str).tolist()}})\n",
+                self.main_table_name
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn take_statement(&mut self, count: u32) {
+            let code_gen = format!(
+                "{} = {}.head({})\n",
+                self.main_table_name, self.main_table_name, count
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn top_statement(&mut self, count: u32, keys: &[SortKey]) {
+            let code_gen = format!("{} = {}", self.main_table_name, self.main_table_name);
+            self.python_output.push_str(&code_gen);
+            self.sort_values(keys);
+            self.python_output.push_str(&format!(".head({})\n", count));
+        }
+
+        fn sample_statement(&mut self, count: u32, seed: Option<u32>) {
+            // Like KQL, smaller tables are returned whole rather than raising an error.
+            let code_gen = format!(
+                "{0} = {0}.sample(n=min({1}, len({0}))",
+                self.main_table_name, count
+            );
+            self.python_output.push_str(&code_gen);
+            if let Some(seed) = seed {
+                self.python_output
+                    .push_str(&format!(", random_state={}", seed));
+            }
+            self.python_output.push_str(")\n");
+        }
+
+        fn join_statement(&mut self, kind: &JoinKind, right: &Program, on: &[JoinKey]) {
+            // The right table is built first, in its own variable.
+            let main_table_name = std::mem::replace(&mut self.main_table_name, right.table.clone());
+            self.query(right);
+            self.main_table_name = main_table_name;
+
+            let left_keys: Vec<Column> = on.iter().map(|key| key.left.clone()).collect();
+            let right_keys: Vec<Column> = on.iter().map(|key| key.right.clone()).collect();
+
+            let how = match kind {
+                JoinKind::Inner => "inner",
+                JoinKind::LeftOuter => "left",
+                JoinKind::RightOuter => "right",
+                JoinKind::FullOuter => "outer",
+                JoinKind::LeftAnti | JoinKind::LeftSemi => {
+                    // Semi and anti joins filter the left table, without adding any column to it.
+                    let code_gen = format!(
+                        "cond = {}.set_index({}).index.isin({}.set_index({}).index)\n",
+                        self.main_table_name,
+                        column_names(&left_keys),
+                        right.table,
+                        column_names(&right_keys)
+                    );
+                    self.python_output.push_str(&code_gen);
+                    let negation = match kind {
+                        JoinKind::LeftAnti => "~",
+                        _ => "",
+                    };
+                    let code_gen = format!(
+                        "{} = {}[{}cond]\n",
+                        self.main_table_name, self.main_table_name, negation
+                    );
+                    self.python_output.push_str(&code_gen);
+                    return;
+                }
+            };
+
+            let keys = match left_keys == right_keys {
+                true => format!("on={}", column_names(&left_keys)),
+                false => format!(
+                    "left_on={}, right_on={}",
+                    column_names(&left_keys),
+                    column_names(&right_keys)
+                ),
+            };
+            // Right columns sharing a name with a left column are suffixed with 1, as in KQL.
+            let code_gen = format!(
+                "{} = {}.merge({}, how=\"{}\", {}, suffixes=(\"\", \"1\"))\n",
+                self.main_table_name, self.main_table_name, right.table, how, keys
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        /// Emits the `.sort_values(...)` call sorting by the keys.
+        fn sort_values(&mut self, keys: &[SortKey]) {
+            let columns: Vec<Column> = keys.iter().map(|key| key.column.clone()).collect();
+            let ascending: Vec<&str> = keys
+                .iter()
+                .map(|key| match key.ascending {
+                    true => "True",
+                    false => "False",
+                })
+                .collect();
+            let code_gen = format!(
+                ".sort_values(by={}, ascending=[{}]",
+                column_names(&columns),
+                ascending.join(", ")
+            );
+            self.python_output.push_str(&code_gen);
+            // The parser ensures that all keys placing nulls explicitly agree with each other.
+            match keys.iter().find_map(|key| key.nulls) {
+                Some(NullsPosition::First) => {
+                    self.python_output.push_str(", na_position=\"first\"")
+                }
+                Some(NullsPosition::Last) => self.python_output.push_str(", na_position=\"last\""),
+                None => {}
+            }
+            self.python_output.push(')');
+        }
+
+        fn expression(&mut self, expression: &Expression) {
+            match expression {
+                Expression::Column(column) => self.column(column),
+                Expression::Literal(literal) => self.literal(literal),
+                Expression::Unary {
+                    operator: UnaryOperator::Not,
+                    operand,
+                } => {
+                    self.python_output.push_str("~(");
+                    self.expression(operand);
+                    self.python_output.push(')');
+                }
+                Expression::Unary { operator, operand } => {
+                    if operator == &UnaryOperator::Minus {
+                        self.python_output.push_str("-1 * ");
+                    }
+                    // The unary prefix binds like a multiplication.
+                    self.operand(operand, BinaryOperator::Multiply.precedence(), false);
+                }
+                Expression::Binary {
+                    operator,
+                    left,
+                    right,
+                } if operator.is_logical() => {
+                    self.logical_operand(left, operator, false);
+                    self.python_output.push_str(match operator {
+                        BinaryOperator::And => " & ",
+                        _ => " | ",
+                    });
+                    self.logical_operand(right, operator, true);
+                }
+                Expression::Binary {
+                    operator,
+                    left,
+                    right,
+                } => {
+                    self.operand(left, operator.precedence(), false);
+                    self.python_output.push_str(match operator {
+                        BinaryOperator::Add => "+",
+                        BinaryOperator::Subtract => "-",
+                        BinaryOperator::Multiply => "*",
+                        BinaryOperator::Divide => "/",
+                        BinaryOperator::GreaterThan => ">",
+                        BinaryOperator::GreaterThanEqualsTo => ">=",
+                        BinaryOperator::LessThan => "<",
+                        BinaryOperator::LessThanEqualsTo => "<=",
+                        BinaryOperator::EqualsTo => "==",
+                        BinaryOperator::NotEqualsTo => "!=",
+                        BinaryOperator::And | BinaryOperator::Or => unreachable!(),
+                    });
+                    self.operand(right, operator.precedence(), true);
+                }
+                Expression::IsNotNull(column) => {
+                    self.column(column);
+                    self.python_output.push_str(".notna()");
+                }
+                Expression::IsNull(column) => {
+                    self.column(column);
+                    self.python_output.push_str(".isna()");
+                }
+            }
+        }
+
+        /// Emits an operand of a binary operator, wrapping it in brackets when it binds looser than its parent.
+        /// Right operands are also bracketed on equal precedence, since all binary operators are left-associative.
+        /// Comparisons are bracketed on both sides of another comparison, which Python would otherwise chain.
+        fn operand(&mut self, operand: &Expression, parent_precedence: u8, is_right: bool) {
+            let needs_brackets = match operand {
+                Expression::Binary { operator, .. } => {
+                    operator.precedence() < parent_precedence
+                        || ((is_right || operator.is_comparison())
+                            && operator.precedence() == parent_precedence)
+                }
+                // Negation is emitted as a `-1 * ` multiplication, so it is bracketed like one.
+                Expression::Unary {
+                    operator: UnaryOperator::Minus,
+                    ..
+                } => {
+                    let precedence = BinaryOperator::Multiply.precedence();
+                    precedence < parent_precedence || (is_right && precedence == parent_precedence)
+                }
+                _ => false,
+            };
+
+            if needs_brackets {
+                self.python_output.push('(');
+                self.expression(operand);
+                self.python_output.push(')');
+            } else {
+                self.expression(operand);
+            }
+        }
+
+        /// Emits an operand of `&` / `|`.
+        /// These bind tighter than comparisons in Python, so any binary operand is bracketed,
+        /// except for a left operand chained with the same operator.
+        fn logical_operand(
+            &mut self,
+            operand: &Expression,
+            parent: &BinaryOperator,
+            is_right: bool,
+        ) {
+            let needs_brackets = match operand {
+                Expression::Binary { operator, .. } => is_right || operator != parent,
+                _ => false,
+            };
+
+            if needs_brackets {
+                self.python_output.push('(');
+                self.expression(operand);
+                self.python_output.push(')');
+            } else {
+                self.expression(operand);
+            }
+        }
+
+        fn column(&mut self, column: &Column) {
+            let code_gen = format!(
+                "{}.loc[:,{}]",
+                self.main_table_name,
+                python_string(&column.name)
+            );
+            self.python_output.push_str(&code_gen);
+        }
+
+        fn literal(&mut self, literal: &Literal) {
+            let code_gen = match literal {
+                Literal::Integer(int) => format!("{}", int),
+                Literal::Float(float) => format!("{}", float),
+                Literal::String(string) => python_string(string),
+                Literal::Boolean(true) => "True".to_string(),
+                Literal::Boolean(false) => "False".to_string(),
+            };
+            self.python_output.push_str(&code_gen);
+        }
+    }
+
+    /// Name of the pandas method computing the aggregate.
+    fn aggregate_method(function: AggregateFunction) -> &'static str {
+        match function {
+            AggregateFunction::Count => "count",
+            AggregateFunction::Sum | AggregateFunction::CountIf => "sum",
+            AggregateFunction::Avg => "mean",
+            AggregateFunction::Min => "min",
+            AggregateFunction::Max => "max",
+            AggregateFunction::Median => "median",
+            AggregateFunction::Stdev => "std",
+            AggregateFunction::Variance => "var",
+            AggregateFunction::DCount => "nunique",
+            AggregateFunction::Percentile(_) => "quantile",
+        }
+    }
+
+    /// Converts a percentile into the fraction pandas expects.
+    /// Shifts the decimal point instead of dividing, so that e.g. 0.7 gives 0.007 rather than 0.006999999999999999.
+    fn quantile(percentile: f64) -> f64 {
+        format!("{}e-2", percentile)
+            .parse()
+            .unwrap_or(percentile / 100.0)
+    }
+
+    /// Formats the column names as a Python list of strings.
+    fn column_names(columns: &[Column]) -> String {
+        let names: Vec<String> = columns.iter().map(|c| python_string(&c.name)).collect();
+        format!("[{}]", names.join(", "))
+    }
+
+    /// Quotes the value as a double-quoted Python string literal, escaping it where needed.
+    fn python_string(value: &str) -> String {
+        let mut quoted = String::from('"');
+        for c in value.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::codegen::PythonCodeGen;
+    use crate::ast::ast::{
+        BinaryOperator, Column, Expression, Literal, Program, Statement, UnaryOperator,
+    };
+
+    /// ASTs built by hand (rather than by the parser) must still produce correctly bracketed code.
+    #[test]
+    fn bracketing_test() {
+        let program = Program {
+            table: "sourceTable".to_string(),
+            statements: vec![
+                Statement::Extend {
+                    column: Column {
+                        name: "foo".to_string(),
+                    },
+                    value: Expression::Binary {
+                        operator: BinaryOperator::Multiply,
+                        left: Box::new(Expression::Binary {
+                            operator: BinaryOperator::Add,
+                            left: Box::new(Expression::Column(Column {
+                                name: "bar".to_string(),
+                            })),
+                            right: Box::new(Expression::Literal(Literal::Integer(1))),
+                        }),
+                        right: Box::new(Expression::Binary {
+                            operator: BinaryOperator::Subtract,
+                            left: Box::new(Expression::Literal(Literal::Integer(2))),
+                            right: Box::new(Expression::Literal(Literal::Integer(3))),
+                        }),
+                    },
+                },
+                Statement::Extend {
+                    column: Column {
+                        name: "baz".to_string(),
+                    },
+                    value: Expression::Binary {
+                        operator: BinaryOperator::Divide,
+                        left: Box::new(Expression::Literal(Literal::Integer(2))),
+                        right: Box::new(Expression::Unary {
+                            operator: UnaryOperator::Minus,
+                            operand: Box::new(Expression::Binary {
+                                operator: BinaryOperator::Add,
+                                left: Box::new(Expression::Column(Column {
+                                    name: "bar".to_string(),
+                                })),
+                                right: Box::new(Expression::Literal(Literal::Integer(1))),
+                            }),
+                        }),
+                    },
+                },
+            ],
+        };
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable.loc[:,\"foo\"] = (sourceTable.loc[:,\"bar\"]+1)*(2-3)\nsourceTable.loc[:,\"baz\"] = 2/(-1 * (sourceTable.loc[:,\"bar\"]+1))\n";
+
+        let output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &output);
+    }
+
+    /// `a > 1 == b < 2` would be chained by Python, so the comparisons are bracketed.
+    #[test]
+    fn comparison_bracketing_test() {
+        let program = Program {
+            table: "sourceTable".to_string(),
+            statements: vec![Statement::Where {
+                condition: Expression::Binary {
+                    operator: BinaryOperator::EqualsTo,
+                    left: Box::new(Expression::Binary {
+                        operator: BinaryOperator::GreaterThan,
+                        left: Box::new(Expression::Column(Column {
+                            name: "a".to_string(),
+                        })),
+                        right: Box::new(Expression::Literal(Literal::Integer(1))),
+                    }),
+                    right: Box::new(Expression::Binary {
+                        operator: BinaryOperator::LessThan,
+                        left: Box::new(Expression::Column(Column {
+                            name: "b".to_string(),
+                        })),
+                        right: Box::new(Expression::Literal(Literal::Boolean(false))),
+                    }),
+                },
+            }],
+        };
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \ncond = ((sourceTable.loc[:,\"a\"]>1)==(sourceTable.loc[:,\"b\"]<False))\nsourceTable = sourceTable[cond]\n";
+
+        let output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &output);
+    }
+
+    #[test]
+    fn string_escaping_test() {
+        let program = Program {
+            table: "sourceTable".to_string(),
+            statements: vec![Statement::Extend {
+                column: Column {
+                    name: "say \"hi\"".to_string(),
+                },
+                value: Expression::Literal(Literal::String("C:\\data\n\tend".to_string())),
+            }],
+        };
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable.loc[:,\"say \\\"hi\\\"\"] = \"C:\\\\data\\n\\tend\"\n";
+
+        let output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &output);
+    }
+}
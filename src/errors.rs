@@ -1,7 +1,7 @@
 pub mod BaseErr {
     use std::{error::Error, fmt::Display};
     #[derive(Debug, Clone)]
-    pub(crate) struct BaseErr;
+    pub struct BaseErr;
 
     /// BaseErr type
     /// Root error type - Serves as the terminal source for errors.
@@ -18,88 +18,154 @@ pub mod ParseErr {
     use std::error::Error;
     use std::fmt::Display;
 
-    use crate::lexer::lexer::Token;
+    use crate::lexer::lexer::{Location, Token};
 
+    /// Errors raised by the parser.
+    /// Every variant records the location in the query source at which it was raised.
     #[derive(Debug)]
-    pub(crate) enum ParseErr {
+    pub enum ParseErr {
         WrongToken {
             expected: Vec<Token>,
             actual: Token,
-            source: Box<dyn Error>,
+            location: Location,
+            source: Box<dyn Error + Send + Sync>,
         },
         CustomParseError {
             error_msg: String,
-            source: Box<dyn Error>,
+            location: Location,
+            source: Box<dyn Error + Send + Sync>,
         },
         NoTokenLeftError {
-            source: Box<dyn Error>,
+            location: Location,
+            source: Box<dyn Error + Send + Sync>,
         },
     }
 
-    impl Display for ParseErr {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    impl ParseErr {
+        /// Location in the query source at which the error was raised.
+        pub fn location(&self) -> &Location {
+            match self {
+                ParseErr::WrongToken { location, .. } => location,
+                ParseErr::CustomParseError { location, .. } => location,
+                ParseErr::NoTokenLeftError { location, .. } => location,
+            }
+        }
+
+        /// Description of the error, without its location.
+        pub fn message(&self) -> String {
             match self {
                 ParseErr::WrongToken {
-                    expected,
-                    actual,
-                    source: _,
+                    expected, actual, ..
                 } => {
-                    let mut err_msg = String::from("Expected ");
-                    let mut i = 0;
-                    loop {
-                        let _tok = &expected[i];
-                        if i == expected.len() - 1 {
-                            let msg = format!(
-                                "{:#?} token, got {:#?} token instead.",
-                                expected[i], actual
-                            );
-                            err_msg.push_str(&msg);
-                            break;
-                        } else {
-                            let msg = format!("{:#?} token or ", expected[i]);
-                            err_msg.push_str(&msg);
-                            i += 1;
+                    let mut names: Vec<&str> = Vec::new();
+                    for tok in expected.iter() {
+                        if !names.contains(&tok.kind_name()) {
+                            names.push(tok.kind_name());
                         }
                     }
-
-                    write!(f, "{}", err_msg)
+                    let expected = match names.split_last() {
+                        Some((last, [])) => last.to_string(),
+                        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+                        None => "nothing".to_string(),
+                    };
+                    format!("expected {}, found {}", expected, actual)
                 }
-                ParseErr::CustomParseError {
-                    error_msg,
-                    source: _,
+                ParseErr::CustomParseError { error_msg, .. } => error_msg.clone(),
+                ParseErr::NoTokenLeftError { .. } => "unexpected end of input".to_string(),
+            }
+        }
+
+        /// Suggestion on how to fix the error, if there is one.
+        pub fn hint(&self) -> Option<&'static str> {
+            match self {
+                ParseErr::WrongToken {
+                    expected, actual, ..
                 } => {
-                    write!(
-                        f,
-                        "Error raised when parsing. See error message: {}",
-                        error_msg
-                    )
+                    if actual == &Token::Error {
+                        Some("this character is not part of the query language")
+                    } else if expected.contains(&Token::CloseSquareBracket) {
+                        Some("column names in square brackets must be closed with ']'")
+                    } else if expected.contains(&Token::CloseBracket) {
+                        Some("every '(' must be matched by a closing ')'")
+                    } else if expected.contains(&Token::READ) {
+                        Some("every statement must start with a pipe, e.g. '| WHERE'")
+                    } else if actual == &Token::EOF {
+                        Some("the query ended before the statement was complete")
+                    } else {
+                        None
+                    }
                 }
+                ParseErr::CustomParseError { .. } => None,
                 ParseErr::NoTokenLeftError { .. } => {
-                    write!(f, "No tokens left to be processed!")
+                    Some("the query ended before the statement was complete")
                 }
             }
         }
+
+        /// Renders the error as a compiler-style diagnostic.
+        /// Shows the offending line of the source with the error location underlined, followed by any hint.
+        pub fn render(&self, source: &str) -> String {
+            let location = self.location();
+            let line = source.lines().nth(location.line - 1).unwrap_or("");
+            let gutter = " ".repeat(location.line.to_string().len());
+
+            // Keeping tabs in the padding so that the carets line up with the source line.
+            let padding: String = line
+                .chars()
+                .take(location.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let width = source
+                .get(location.span.clone())
+                .map(|slice| slice.lines().next().unwrap_or("").chars().count())
+                .unwrap_or(0)
+                .max(1);
+
+            let mut rendered = format!(
+                "error: {}\n{} --> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+                self.message(),
+                gutter,
+                location.line,
+                location.column,
+                gutter,
+                location.line,
+                line,
+                gutter,
+                padding,
+                "^".repeat(width),
+            );
+            if let Some(hint) = self.hint() {
+                rendered.push_str(&format!("\n{} = hint: {}", gutter, hint));
+            }
+            rendered
+        }
+    }
+
+    impl Display for ParseErr {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let location = self.location();
+            write!(
+                f,
+                "line {}, column {}: {}",
+                location.line,
+                location.column,
+                self.message()
+            )
+        }
     }
 
     impl Error for ParseErr {
         fn source(&self) -> Option<&(dyn Error + 'static)> {
             match self {
-                ParseErr::WrongToken {
-                    expected: _,
-                    actual: _,
-                    source,
-                } => {
+                ParseErr::WrongToken { source, .. } => {
                     let error = &**source; // Reference to the trait obejct within the Box
                     Some(error)
                 }
-                ParseErr::CustomParseError {
-                    error_msg: _,
-                    source,
-                } => {
+                ParseErr::CustomParseError { source, .. } => {
                     let error = &**source; // Reference to the trait obejct within the Box
                     Some(error)
                 }
-                ParseErr::NoTokenLeftError { source } => {
+                ParseErr::NoTokenLeftError { source, .. } => {
                     let error = &**source; // Reference to the trait obejct within the Box
                     Some(error)
                 }
@@ -111,3 +177,110 @@ pub mod ParseErr {
         }
     }
 }
+
+pub mod TranspileErr {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    use crate::errors::ParseErr::ParseErr;
+
+    /// Error returned by the top-level `transpile` function.
+    #[derive(Debug)]
+    pub enum TranspileErr {
+        /// Every syntax error found in the query, in source order.
+        Parse(Vec<ParseErr>),
+        /// The named table is neither read from a path nor bound to one.
+        UnboundTable(String),
+    }
+
+    impl TranspileErr {
+        /// Renders the error as compiler-style diagnostics. See `ParseErr::render`.
+        pub fn render(&self, source: &str) -> String {
+            match self {
+                TranspileErr::Parse(errors) => errors
+                    .iter()
+                    .map(|err| err.render(source))
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+                TranspileErr::UnboundTable(table) => format!(
+                    "error: {}\n  = hint: give its READ statement a path, or bind it with '--bind {}=<PATH>'",
+                    self, table
+                ),
+            }
+        }
+    }
+
+    impl Display for TranspileErr {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                TranspileErr::Parse(errors) => {
+                    let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+                    write!(f, "{}", messages.join("\n"))
+                }
+                TranspileErr::UnboundTable(table) => {
+                    write!(f, "no input path for table '{}'", table)
+                }
+            }
+        }
+    }
+
+    impl Error for TranspileErr {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                TranspileErr::Parse(errors) => {
+                    errors.first().map(|err| err as &(dyn Error + 'static))
+                }
+                TranspileErr::UnboundTable(_) => None,
+            }
+        }
+    }
+
+    impl From<Vec<ParseErr>> for TranspileErr {
+        fn from(errors: Vec<ParseErr>) -> Self {
+            TranspileErr::Parse(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::BaseErr::BaseErr;
+    use super::ParseErr::ParseErr;
+    use crate::lexer::lexer::{Location, Token};
+
+    #[test]
+    fn render_test() {
+        let source = "sourceTable\n| WHERE [\"foo\" 5";
+        let err = ParseErr::WrongToken {
+            expected: vec![Token::CloseSquareBracket],
+            actual: Token::Integer(5),
+            location: Location::new(source, 27..28),
+            source: Box::new(BaseErr {}),
+        };
+
+        let expected_output = "error: expected ']', found integer 5\n  --> line 2, column 16\n  |\n2 | | WHERE [\"foo\" 5\n  |                ^\n  = hint: column names in square brackets must be closed with ']'";
+
+        assert_eq!(expected_output, err.render(source));
+        assert_eq!(
+            "line 2, column 16: expected ']', found integer 5",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn message_test() {
+        let source = "sourceTable\n| READ";
+        let err = ParseErr::WrongToken {
+            expected: vec![Token::READ, Token::WHERE, Token::EXTEND],
+            actual: Token::Identity("foo".to_string()),
+            location: Location::new(source, 0..11),
+            source: Box::new(BaseErr {}),
+        };
+
+        assert_eq!(
+            "expected '| READ', '| WHERE' or '| EXTEND', found identifier 'foo'",
+            err.message()
+        );
+    }
+}
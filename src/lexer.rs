@@ -1,17 +1,48 @@
 pub mod lexer {
 
+    use std::fmt::Display;
+    use std::ops::Range;
+
     use logos::{self, Lexer};
 
+    /// Byte range of a token within the query source.
+    pub type Span = Range<usize>;
+
     fn capture_string(lex: &mut Lexer<Token>) -> Option<String> {
         let captured_string = lex.slice();
         Some(captured_string.to_string())
     }
-    fn capture_float(lex: &mut Lexer<Token>) -> Option<f32> {
+    /// Strips the surrounding quotes from a string literal and resolves its escape sequences.
+    /// Returns None for unknown escape sequences, which the lexer then reports as an error.
+    fn capture_string_literal(lex: &mut Lexer<Token>) -> Option<String> {
+        let captured_string = lex.slice();
+        let content = &captured_string[1..captured_string.len() - 1];
+
+        let mut unescaped = String::with_capacity(content.len());
+        let mut chars = content.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+            match chars.next()? {
+                '"' => unescaped.push('"'),
+                '\'' => unescaped.push('\''),
+                '\\' => unescaped.push('\\'),
+                'n' => unescaped.push('\n'),
+                'r' => unescaped.push('\r'),
+                't' => unescaped.push('\t'),
+                _ => return None,
+            }
+        }
+        Some(unescaped)
+    }
+    fn capture_float(lex: &mut Lexer<Token>) -> Option<f64> {
         let captured_string = lex.slice();
         let captured_float = captured_string.parse();
         captured_float.ok()
     }
-    fn capture_int(lex: &mut Lexer<Token>) -> Option<i32> {
+    fn capture_int(lex: &mut Lexer<Token>) -> Option<i64> {
         let captured_string = lex.slice();
         let captured_int = captured_string.parse();
         captured_int.ok()
@@ -38,6 +69,10 @@ pub mod lexer {
         OpenBracket,
         #[token(")")]
         CloseBracket,
+        #[token(",")]
+        Comma,
+        #[token(".")]
+        Dot,
         #[token(">")]
         GreaterThan,
         #[token("<")]
@@ -46,24 +81,81 @@ pub mod lexer {
         GreaterThanEqualsTo,
         #[token("<=")]
         LessThanEqualsTo,
-        #[token(r#"""#)]
-        QuotationMark,
+        #[token("==")]
+        EqualsTo,
+        #[token("!=")]
+        #[token("<>")]
+        NotEqualsTo,
         #[token("| READ")]
         READ,
         #[token("| WHERE")]
         WHERE,
         #[token("| EXTEND")]
         EXTEND,
+        #[token("| PROJECT")]
+        PROJECT,
+        #[token("| PROJECT-AWAY")]
+        PROJECTAWAY,
+        #[token("| PROJECT-RENAME")]
+        PROJECTRENAME,
+        #[token("| SORT")]
+        #[token("| ORDER")]
+        SORT,
+        #[token("| DISTINCT")]
+        DISTINCT,
+        #[token("| SUMMARIZE")]
+        SUMMARIZE,
+        #[token("| COUNT")]
+        COUNT,
+        #[token("| GETSCHEMA")]
+        GETSCHEMA,
+        #[token("| TAKE")]
+        #[token("| LIMIT")]
+        TAKE,
+        #[token("| TOP")]
+        TOP,
+        #[token("| SAMPLE")]
+        SAMPLE,
+        #[token("| JOIN")]
+        JOIN,
+        #[token("| WRITE")]
+        #[token("| OUTPUT")]
+        WRITE,
         #[token("isnotnull")]
         ISNOTNULL,
         #[token("isnull")]
         ISNULL,
-        #[regex(r#"([A-z]+[0-9]*)"#, capture_string)]
+        #[token("AND")]
+        #[token("and")]
+        AND,
+        #[token("OR")]
+        #[token("or")]
+        OR,
+        #[token("ON")]
+        #[token("on")]
+        ON,
+        #[token("$left")]
+        LeftTable,
+        #[token("$right")]
+        RightTable,
+        #[token("BY")]
+        #[token("by")]
+        BY,
+        #[token("NOT")]
+        #[token("not")]
+        NOT,
+        #[token("true", |_| true)]
+        #[token("false", |_| false)]
+        Boolean(bool),
+        #[regex(r#"[A-Za-z_][A-Za-z0-9_]*"#, capture_string)]
         Identity(String),
+        #[regex(r#""([^"\\]|\\.)*""#, capture_string_literal)]
+        #[regex(r#"'([^'\\]|\\.)*'"#, capture_string_literal)]
+        StringLiteral(String),
         #[regex(r#"[0-9]+"#, capture_int)]
-        Integer(i32),
-        #[regex(r#"[0-9]+.[0-9]+"#, capture_float)]
-        Float(f32),
+        Integer(i64),
+        #[regex(r#"[0-9]+\.[0-9]+"#, capture_float)]
+        Float(f64),
         // #[regex(r#"\n"#)]
         // NewLine,sd
         EOF,
@@ -71,6 +163,129 @@ pub mod lexer {
         #[regex(r#"[\n\t\s]"#, logos::skip)]
         Error,
     }
+
+    impl Token {
+        /// Returns true if the token starts a new statement of the pipeline.
+        pub fn is_statement(&self) -> bool {
+            matches!(
+                self,
+                Token::READ
+                    | Token::WHERE
+                    | Token::EXTEND
+                    | Token::PROJECT
+                    | Token::PROJECTAWAY
+                    | Token::PROJECTRENAME
+                    | Token::SORT
+                    | Token::DISTINCT
+                    | Token::SUMMARIZE
+                    | Token::COUNT
+                    | Token::GETSCHEMA
+                    | Token::TAKE
+                    | Token::TOP
+                    | Token::SAMPLE
+                    | Token::JOIN
+                    | Token::WRITE
+            )
+        }
+
+        /// Human-readable name of the kind of token, ignoring any captured value.
+        /// Used when listing the tokens the parser expected.
+        pub fn kind_name(&self) -> &'static str {
+            match self {
+                Token::PlusOperator => "'+'",
+                Token::MinusOperator => "'-'",
+                Token::MulOperator => "'*'",
+                Token::DivOperator => "'/'",
+                Token::EqualsOperator => "'='",
+                Token::OpenSquareBracket => "'['",
+                Token::CloseSquareBracket => "']'",
+                Token::OpenBracket => "'('",
+                Token::CloseBracket => "')'",
+                Token::Comma => "','",
+                Token::Dot => "'.'",
+                Token::GreaterThan => "'>'",
+                Token::LessThan => "'<'",
+                Token::GreaterThanEqualsTo => "'>='",
+                Token::LessThanEqualsTo => "'<='",
+                Token::EqualsTo => "'=='",
+                Token::NotEqualsTo => "'!='",
+                Token::READ => "'| READ'",
+                Token::WHERE => "'| WHERE'",
+                Token::EXTEND => "'| EXTEND'",
+                Token::PROJECT => "'| PROJECT'",
+                Token::PROJECTAWAY => "'| PROJECT-AWAY'",
+                Token::PROJECTRENAME => "'| PROJECT-RENAME'",
+                Token::SORT => "'| SORT'",
+                Token::DISTINCT => "'| DISTINCT'",
+                Token::SUMMARIZE => "'| SUMMARIZE'",
+                Token::COUNT => "'| COUNT'",
+                Token::GETSCHEMA => "'| GETSCHEMA'",
+                Token::TAKE => "'| TAKE'",
+                Token::TOP => "'| TOP'",
+                Token::SAMPLE => "'| SAMPLE'",
+                Token::JOIN => "'| JOIN'",
+                Token::WRITE => "'| WRITE'",
+                Token::ISNOTNULL => "'isnotnull'",
+                Token::ISNULL => "'isnull'",
+                Token::AND => "'AND'",
+                Token::OR => "'OR'",
+                Token::ON => "'ON'",
+                Token::LeftTable => "'$left'",
+                Token::RightTable => "'$right'",
+                Token::BY => "'BY'",
+                Token::NOT => "'NOT'",
+                Token::Boolean(_) => "boolean",
+                Token::Identity(_) => "identifier",
+                Token::StringLiteral(_) => "string",
+                Token::Integer(_) => "integer",
+                Token::Float(_) => "float",
+                Token::EOF => "end of input",
+                Token::Error => "unrecognised character",
+            }
+        }
+    }
+
+    /// Describes the token as it appeared in the query, including any captured value.
+    impl Display for Token {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Token::Identity(identity) => write!(f, "identifier '{}'", identity),
+                Token::StringLiteral(string) => write!(f, "string {:?}", string),
+                Token::Integer(int) => write!(f, "integer {}", int),
+                Token::Float(float) => write!(f, "float {}", float),
+                Token::Boolean(boolean) => write!(f, "boolean {}", boolean),
+                other => write!(f, "{}", other.kind_name()),
+            }
+        }
+    }
+
+    /// A token paired with its position in the query source.
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct SpannedToken {
+        pub token: Token,
+        pub span: Span,
+    }
+
+    /// Human-readable position of a span within the query source.
+    /// Lines and columns are 1-based, with columns counted in characters.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct Location {
+        pub span: Span,
+        pub line: usize,
+        pub column: usize,
+    }
+
+    impl Location {
+        /// Computes the line and column of the start of the span within the source.
+        pub fn new(source: &str, span: Span) -> Self {
+            let start = span.start.min(source.len());
+            let preceding = &source[..start];
+            let line = preceding.matches('\n').count() + 1;
+            let line_start = preceding.rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let column = preceding[line_start..].chars().count() + 1;
+            Location { span, line, column }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -91,4 +306,59 @@ mod tests {
             dbg!(token);
         }
     }
+
+    #[test]
+    fn string_literal_test() {
+        let input = r#"["foo  bar"] 'Pump-01 (h)' "say \"hi\"\n" 'it\'s' "C:\\data" "bad\q""#;
+        let lex = <lexer::Token as logos::Logos>::lexer(input);
+        let tokens: Vec<_> = lex.collect();
+
+        assert_eq!(
+            vec![
+                lexer::Token::OpenSquareBracket,
+                lexer::Token::StringLiteral("foo  bar".to_string()),
+                lexer::Token::CloseSquareBracket,
+                lexer::Token::StringLiteral("Pump-01 (h)".to_string()),
+                lexer::Token::StringLiteral("say \"hi\"\n".to_string()),
+                lexer::Token::StringLiteral("it's".to_string()),
+                lexer::Token::StringLiteral("C:\\data".to_string()),
+                lexer::Token::Error,
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn number_test() {
+        let input = "100 1.5 10";
+        let lex = <lexer::Token as logos::Logos>::lexer(input);
+        let tokens: Vec<_> = lex.collect();
+
+        assert_eq!(
+            vec![
+                lexer::Token::Integer(100),
+                lexer::Token::Float(1.5),
+                lexer::Token::Integer(10),
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn location_test() {
+        let input = "sourceTable\n| READ csv\n| WHERE [\"foo\"] > 5";
+
+        let lex = <lexer::Token as logos::Logos>::lexer(input);
+        let spans: Vec<_> = lex.spanned().map(|(_, span)| span).collect();
+
+        let location = lexer::Location::new(input, spans[0].clone());
+        assert_eq!((location.line, location.column), (1, 1));
+
+        let location = lexer::Location::new(input, spans[4].clone());
+        assert_eq!((location.line, location.column), (3, 9));
+
+        let end = input.len();
+        let location = lexer::Location::new(input, end..end);
+        assert_eq!((location.line, location.column), (3, 20));
+    }
 }
@@ -0,0 +1,135 @@
+//! Transpiles pipe-based dataframe queries into Python (pandas) code.
+//!
+//! The [`transpile`] function is the entry point for most users. The lexer, parser, AST and code generator
+//! are also exposed for tooling that needs to work with the query structure directly.
+
+// Modules follow a `<file>::<file>` layout, and the tokens/error modules mirror the query keywords and type names.
+#![allow(clippy::module_inception, clippy::upper_case_acronyms, non_snake_case)]
+
+use std::collections::HashMap;
+
+pub mod ast;
+pub mod codegen;
+pub mod errors;
+pub mod lexer;
+pub mod parser;
+
+pub use crate::codegen::codegen::PythonCodeGen;
+pub use crate::errors::ParseErr::ParseErr;
+pub use crate::errors::TranspileErr::TranspileErr as Error;
+pub use crate::lexer::lexer::Token;
+pub use crate::parser::parser::RustyParser;
+
+/// Options controlling how a query is transpiled.
+/// Use `TranspileOptions::default()` for the default behaviour.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct TranspileOptions {
+    bindings: HashMap<String, String>,
+    placeholder: Option<String>,
+}
+
+impl TranspileOptions {
+    /// Binds a table to the path of the file it is read from.
+    /// Used by READ statements which do not specify a path themselves.
+    pub fn bind(mut self, table: impl Into<String>, path: impl Into<String>) -> Self {
+        self.bindings.insert(table.into(), path.into());
+        self
+    }
+
+    /// Emits the placeholder, as is, in place of the path of tables which are neither read from a path nor bound.
+    /// Without a placeholder, such tables are an error.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+}
+
+/// Transpiles the query in `source` into Python code.
+pub fn transpile(source: &str, options: &TranspileOptions) -> Result<String, Error> {
+    let lex = <Token as logos::Logos>::lexer(source);
+    let mut pars = RustyParser::new(lex);
+    let program = pars.program()?;
+
+    let mut codegen = PythonCodeGen::new();
+    for (table, path) in options.bindings.iter() {
+        codegen = codegen.bind(table, path);
+    }
+    if let Some(placeholder) = &options.placeholder {
+        codegen = codegen.placeholder(placeholder);
+    }
+    codegen.generate(&program)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{transpile, Error, TranspileOptions};
+
+    #[test]
+    fn transpile_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE ["foo"] > 1
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (sourceTable.loc[:,\"foo\"]>1)\nsourceTable = sourceTable[cond]\n";
+
+        let options = TranspileOptions::default().placeholder("<filepath>");
+        let python_output = transpile(input, &options).unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn transpile_error_test() {
+        let input = r#"
+        sourceTable
+        | READ foo
+        "#;
+
+        let result = transpile(input, &TranspileOptions::default());
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn unbound_table_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        "#;
+
+        let result = transpile(input, &TranspileOptions::default());
+        assert!(matches!(result, Err(Error::UnboundTable(table)) if table == "sourceTable"));
+    }
+
+    /// Errors must be able to cross threads, e.g. when converted into `Box<dyn Error + Send + Sync>`.
+    #[test]
+    fn error_send_sync_test() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Error>();
+    }
+
+    /// The example query shipped with the repository must always transpile.
+    #[test]
+    fn example_test() {
+        let input = include_str!("../examples/simple_input.txt");
+        assert!(transpile(input, &TranspileOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn bind_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = \"data.csv\" \nsourceTable = pd.read_csv(sourceTable) \n";
+
+        let options = TranspileOptions::default().bind("sourceTable", "data.csv");
+        let python_output = transpile(input, &options).unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+}
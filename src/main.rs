@@ -1,7 +1,185 @@
-mod errors;
-mod lexer;
-mod parser;
+use std::io::{Read, Write};
+use std::process::ExitCode;
 
-fn main() {
-    println!("Hello, world!");
+use python_dataframe_parser::{transpile, Error, TranspileOptions};
+
+const USAGE: &str = "\
+Usage: python_dataframe_parser [INPUT] [--output <FILE>] [--bind <TABLE>=<PATH>]...
+
+Transpiles a query into Python dataframe code.
+
+Arguments:
+  [INPUT]                File containing the query. Reads from stdin if omitted or '-'.
+
+Options:
+  -o, --output <FILE>    Writes the generated code to FILE instead of stdout.
+  -b, --bind <TABLE>=<PATH>
+                         Reads TABLE from PATH when its READ statement has no path. Can be repeated.
+  -h, --help             Prints this message.";
+
+/// Command-line arguments of the transpiler.
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    input: Option<String>,
+    output: Option<String>,
+    bindings: Vec<(String, String)>,
+    help: bool,
+}
+
+impl Args {
+    /// Parses the arguments following the program name.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => parsed.help = true,
+                "-o" | "--output" => match args.next() {
+                    Some(path) => parsed.output = Some(path),
+                    None => return Err(format!("'{}' requires a file path.", arg)),
+                },
+                "-b" | "--bind" => match args.next().as_deref().and_then(|b| b.split_once('=')) {
+                    Some((table, path)) if !table.is_empty() => {
+                        parsed.bindings.push((table.to_string(), path.to_string()))
+                    }
+                    _ => return Err(format!("'{}' requires a <TABLE>=<PATH> binding.", arg)),
+                },
+                "-" => parsed.input = None,
+                other if other.starts_with('-') => {
+                    return Err(format!("Unknown option '{}'.", other))
+                }
+                other => {
+                    if parsed.input.is_some() {
+                        return Err(format!("Unexpected argument '{}'.", other));
+                    }
+                    parsed.input = Some(other.to_string());
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if args.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let source = match read_input(args.input.as_deref()) {
+        Ok(source) => source,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut options = TranspileOptions::default();
+    for (table, path) in args.bindings.iter() {
+        options = options.bind(table, path);
+    }
+
+    let python_output = match transpile(&source, &options) {
+        Ok(python_output) => python_output,
+        Err(err) => {
+            eprintln!("{}", err.render(&source));
+            if let Error::Parse(errors) = &err {
+                if errors.len() > 1 {
+                    eprintln!("\nerror: aborting due to {} errors", errors.len());
+                }
+            }
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match write_output(args.output.as_deref(), &python_output) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Reads the query from the input file, or from stdin if no file was given.
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    match path {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read '{}': {}", path, err)),
+        None => {
+            let mut source = String::new();
+            std::io::stdin()
+                .read_to_string(&mut source)
+                .map_err(|err| format!("Unable to read from stdin: {}", err))?;
+            Ok(source)
+        }
+    }
+}
+
+/// Writes the generated code to the output file, or to stdout if no file was given.
+fn write_output(path: Option<&str>, python_output: &str) -> Result<(), String> {
+    match path {
+        Some(path) => std::fs::write(path, python_output)
+            .map_err(|err| format!("Unable to write '{}': {}", path, err)),
+        None => std::io::stdout()
+            .write_all(python_output.as_bytes())
+            .map_err(|err| format!("Unable to write to stdout: {}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Args;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn args_test() {
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+        assert_eq!(
+            parse(&["query.txt", "--output", "out.py"]).unwrap(),
+            Args {
+                input: Some("query.txt".to_string()),
+                output: Some("out.py".to_string()),
+                bindings: Vec::new(),
+                help: false,
+            }
+        );
+        assert_eq!(
+            parse(&["-o", "out.py", "-"]).unwrap(),
+            Args {
+                input: None,
+                output: Some("out.py".to_string()),
+                bindings: Vec::new(),
+                help: false,
+            }
+        );
+        assert_eq!(
+            parse(&["--bind", "sites=data/sites.csv", "-b", "a=b=c"])
+                .unwrap()
+                .bindings,
+            vec![
+                ("sites".to_string(), "data/sites.csv".to_string()),
+                ("a".to_string(), "b=c".to_string()),
+            ]
+        );
+        assert!(parse(&["--bind", "sites"]).is_err());
+        assert!(parse(&["--bind", "=data.csv"]).is_err());
+        assert!(parse(&["--output"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["a.txt", "b.txt"]).is_err());
+    }
 }
@@ -1,17 +1,22 @@
-//! Contains the parser module used for parsing the tokens into Python dataframe code.
+//! Contains the parser module used for parsing the tokens into the AST.
+//! See the codegen module for turning the AST into Python dataframe code.
 pub mod parser {
     use std::collections::HashSet;
-    
-    
 
     use logos::Lexer;
 
+    use crate::ast::ast::{
+        AggregateFunction, Aggregation, BinaryOperator, Column, Expression, FileFormat,
+        FormatOption, JoinKey, JoinKind, Literal, NullsPosition, Program, ProjectColumn, Rename,
+        SortKey, Statement, UnaryOperator,
+    };
     use crate::errors::BaseErr::BaseErr;
     use crate::errors::ParseErr::ParseErr;
     // use crate::errors::{ParseErr};
-    use crate::lexer::lexer::Token;
+    use crate::lexer::lexer::{Location, SpannedToken, Token};
 
     #[derive(Eq, Hash, PartialEq)]
+    #[allow(dead_code)]
     pub(crate) enum EntityType {
         Table(String),
         Column(String),
@@ -21,18 +26,35 @@ pub mod parser {
     /// Lifetime of the parser is tied to the lifetime of the lexer.
     /// Hence, the lexer must live at least as long as the parser.
     pub struct RustyParser<'a> {
-        current_token: Option<Token>,
-        next_token: Option<Token>,
+        current_token: Option<SpannedToken>,
+        next_token: Option<SpannedToken>,
         lexer: logos::Lexer<'a, Token>,
         entities: HashSet<EntityType>,
-        main_table_name: String,
-        pub(crate) python_output: String,
     }
 
     impl<'a> RustyParser<'a> {
+        /// Returns the current token, without its span.
+        fn current(&self) -> Option<&Token> {
+            self.current_token.as_ref().map(|spanned| &spanned.token)
+        }
+
+        /// Returns the location of the current token.
+        /// Points just past the last non-whitespace character once all tokens have been consumed.
+        fn location(&self) -> Location {
+            let source = self.lexer.source();
+            let span = match self.current_token.as_ref() {
+                Some(spanned) => spanned.span.clone(),
+                None => {
+                    let end = source.trim_end().len();
+                    end..end
+                }
+            };
+            Location::new(source, span)
+        }
+
         /// Returns true if the current token matches the input token. Returns false otherwise.
         fn check_token(&self, kind: &Token) -> bool {
-            if let Some(token) = self.current_token.as_ref() {
+            if let Some(token) = self.current() {
                 let result = token == kind;
                 return result;
             }
@@ -41,7 +63,7 @@ pub mod parser {
 
         /// Returns true if the next token matches the input token. Returns false otherwise.
         fn check_next_token(&self, kind: &Token) -> bool {
-            if let Some(token) = self.next_token.as_ref() {
+            if let Some(token) = self.next_token.as_ref().map(|spanned| &spanned.token) {
                 let result = token == kind;
                 return result;
             }
@@ -56,14 +78,17 @@ pub mod parser {
                     self.move_token();
                     Ok(())
                 }
-                false => match self.current_token.as_ref() {
+                false => match self.current() {
                     Some(i) => Err(ParseErr::WrongToken {
                         expected: vec![kind.clone()],
                         actual: i.clone(),
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     }),
-                    None => Err(ParseErr::CustomParseError {
-                        error_msg: "".to_string(),
+                    None => Err(ParseErr::WrongToken {
+                        expected: vec![kind.clone()],
+                        actual: Token::EOF,
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     }),
                 },
@@ -73,7 +98,10 @@ pub mod parser {
         /// Replaces the current token with the next token.
         /// Replaces the next token with the following token.
         fn move_token(&mut self) {
-            let following_token = self.lexer.next();
+            let following_token = self.lexer.next().map(|token| SpannedToken {
+                token,
+                span: self.lexer.span(),
+            });
             self.current_token = self.next_token.take();
             self.next_token = following_token;
         }
@@ -84,498 +112,2358 @@ pub mod parser {
                 next_token: None,
                 lexer: lex,
                 entities: HashSet::new(),
-                main_table_name: "".to_string(),
-                python_output: "".to_string(),
             };
             parser.move_token(); // Moving the first token value into the "next_token" field of the struct.
             parser.move_token(); // Moving the second token value into the "next_token" field of the struct + moving the first token value into the "current_token" field of the struct.
             parser
         }
 
-        pub fn program(&mut self) -> Result<(), ParseErr> {
+        /// Parses the whole query.
+        /// A syntax error does not stop the parse: the parser skips ahead to the next statement and carries on,
+        /// so that every error in the query is reported at once.
+        pub fn program(&mut self) -> Result<Program, Vec<ParseErr>> {
+            let mut errors = Vec::new();
+
+            let table = match self.table() {
+                Ok(table) => {
+                    if let Err(err) = self.starts_with_read() {
+                        errors.push(err);
+                    }
+                    table
+                }
+                Err(err) => {
+                    errors.push(err);
+                    self.synchronise();
+                    "".to_string()
+                }
+            };
+
+            let mut statements = Vec::new();
+            while self.current_token.is_some() {
+                match self.statement(statements.is_empty()) {
+                    Ok(statement) => statements.push(statement),
+                    Err(err) => {
+                        errors.push(err);
+                        self.synchronise();
+                    }
+                }
+            }
+
+            match errors.is_empty() {
+                true => Ok(Program { table, statements }),
+                false => Err(errors),
+            }
+        }
+
+        /// Checks that the statements after the table name start with a READ.
+        /// Until then, the table variable only holds the path of its file.
+        fn starts_with_read(&self) -> Result<(), ParseErr> {
+            match self.check_token(&Token::READ) {
+                true => Ok(()),
+                false => Err(ParseErr::CustomParseError {
+                    error_msg: "Expected the query to start with '| READ'.".to_string(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
+        }
+
+        /// Skips tokens until the start of the next statement, or until the end of the input.
+        fn synchronise(&mut self) {
+            while let Some(tok) = self.current() {
+                if tok.is_statement() {
+                    break;
+                }
+                self.move_token();
+            }
+        }
+
+        fn table(&mut self) -> Result<String, ParseErr> {
             // Initial token - Should be an identity that represents a token name
-            let token = self.current_token.as_ref();
+            let token = self.current();
             match token {
                 Some(tok) => match tok {
                     Token::Identity(identity) => {
+                        let identity = identity.clone();
                         self.entities.insert(EntityType::Table(identity.clone())); // Adding into the HashSet of tables
-                        self.main_table_name.push_str(identity); // Indicating that this will be the main table.
-                        let gen_code = format!("{} = <filepath> \n", identity);
-                        self.python_output.push_str(&gen_code);
                         self.move_token();
+                        Ok(identity)
                     }
-                    other => {
-                        return Err(ParseErr::WrongToken {
-                            expected: vec![Token::Identity("<variable name>".to_string())],
-                            actual: other.clone(),
-                            source: Box::new(BaseErr {}),
-                        })
-                    }
-                },
-                None => {
-                    return Err(ParseErr::CustomParseError {
-                        error_msg: "Expected a identity for the first input.".to_string(),
+                    other => Err(ParseErr::WrongToken {
+                        expected: vec![Token::Identity("<variable name>".to_string())],
+                        actual: other.clone(),
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
-                    })
-                }
-            }
-
-            while self.current_token.is_some() {
-                self.statement()?;
+                    }),
+                },
+                None => Err(ParseErr::CustomParseError {
+                    error_msg: "Expected a identity for the first input.".to_string(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
             }
-
-            Ok(())
         }
 
-        fn statement(&mut self) -> Result<(), ParseErr> {
-            match self.current_token.as_ref() {
+        /// Parses the next statement. `is_first` tells whether it is the first statement of its query.
+        fn statement(&mut self, is_first: bool) -> Result<Statement, ParseErr> {
+            match self.current() {
                 Some(Token::READ) => {
+                    let location = self.location();
                     self.move_token();
-                    self.read_statement()?;
-                    Ok(())
+                    let statement = self.read_statement()?;
+                    // Later in the query, the table variable holds a DataFrame rather than the path of a file.
+                    if let Statement::Read { path: None, .. } = statement {
+                        if !is_first {
+                            return Err(ParseErr::CustomParseError {
+                                error_msg: "Only the first READ of a query may omit the path."
+                                    .to_string(),
+                                location,
+                                source: Box::new(BaseErr {}),
+                            });
+                        }
+                    }
+                    Ok(statement)
                 }
                 Some(Token::WHERE) => {
                     self.move_token();
-                    self.where_statement()?;
-                    Ok(())
+                    self.where_statement()
                 }
                 Some(Token::EXTEND) => {
                     self.move_token();
-                    self.extend_statement()?;
-                    Ok(())
+                    self.extend_statement()
+                }
+                Some(Token::PROJECT) => {
+                    self.move_token();
+                    self.project_statement()
+                }
+                Some(Token::PROJECTAWAY) => {
+                    self.move_token();
+                    let columns = self.column_list()?;
+                    Ok(Statement::ProjectAway { columns })
+                }
+                Some(Token::PROJECTRENAME) => {
+                    self.move_token();
+                    self.project_rename_statement()
+                }
+                Some(Token::SORT) => {
+                    self.move_token();
+                    self.sort_statement()
+                }
+                Some(Token::DISTINCT) => {
+                    self.move_token();
+                    self.distinct_statement()
+                }
+                Some(Token::SUMMARIZE) => {
+                    self.move_token();
+                    self.summarize_statement()
+                }
+                Some(Token::COUNT) => {
+                    self.move_token();
+                    Ok(Statement::Count)
+                }
+                Some(Token::GETSCHEMA) => {
+                    self.move_token();
+                    Ok(Statement::GetSchema)
+                }
+                Some(Token::TAKE) => {
+                    self.move_token();
+                    self.take_statement()
+                }
+                Some(Token::TOP) => {
+                    self.move_token();
+                    self.top_statement()
+                }
+                Some(Token::SAMPLE) => {
+                    self.move_token();
+                    self.sample_statement()
+                }
+                Some(Token::JOIN) => {
+                    self.move_token();
+                    self.join_statement()
+                }
+                Some(Token::WRITE) => {
+                    self.move_token();
+                    self.write_statement()
                 }
                 Some(tok) => Err(ParseErr::WrongToken {
-                    expected: vec![Token::READ, Token::WHERE, Token::WHERE],
+                    expected: vec![
+                        Token::READ,
+                        Token::WHERE,
+                        Token::EXTEND,
+                        Token::PROJECT,
+                        Token::PROJECTAWAY,
+                        Token::PROJECTRENAME,
+                        Token::SORT,
+                        Token::DISTINCT,
+                        Token::SUMMARIZE,
+                        Token::COUNT,
+                        Token::GETSCHEMA,
+                        Token::TAKE,
+                        Token::TOP,
+                        Token::SAMPLE,
+                        Token::JOIN,
+                        Token::WRITE,
+                    ],
                     actual: tok.clone(),
+                    location: self.location(),
                     source: Box::new(BaseErr {}),
                 }),
                 None => Err(ParseErr::CustomParseError {
                     error_msg: "Expected a statement!".to_string(),
+                    location: self.location(),
                     source: Box::new(BaseErr {}),
                 }),
             }
         }
 
-        fn read_statement(&mut self) -> Result<(), ParseErr> {
-            match self.current_token.as_ref() {
-                Some(tok) => match tok {
-                    Token::Identity(identity) => match identity.to_lowercase().as_str() {
-                        "csv" => {
-                            let code_gen = format!(
-                                "{} = pd.DataFrame.read_csv({}) \n",
-                                self.main_table_name, self.main_table_name
-                            );
-                            self.python_output.push_str(&code_gen);
-                            self.move_token();
-                            Ok(())
-                        }
-                        "excel" => {
-                            let code_gen = format!(
-                                "{} = pd.DataFrame.read_excel({}) \n",
-                                self.main_table_name, self.main_table_name
-                            );
-                            self.python_output.push_str(&code_gen);
-                            self.move_token();
-                            Ok(())
-                        }
-                        _ => {
-                            Err(ParseErr::CustomParseError {
-                                error_msg: "Expected either 'csv' or 'excel'.".to_string(),
-                                source: Box::new(BaseErr {}),
-                            })
-                        }
-                    },
-                    _other => todo!(),
-                },
-                None => {
-                    Err(ParseErr::CustomParseError {
-                        error_msg: "Expected either 'csv' or 'excel'.".to_string(),
-                        source: Box::new(BaseErr {}),
-                    })
-                }
-            }
+        fn read_statement(&mut self) -> Result<Statement, ParseErr> {
+            let (format, options, path) = self.file_target(FileFormat::read_options)?;
+            Ok(Statement::Read {
+                format,
+                options,
+                path,
+            })
         }
 
-        fn where_statement(&mut self) -> Result<(), ParseErr> {
-            self.python_output.push_str("cond = (");
-            match self.current_token.as_ref() {
-                // NOTE: Error here due to the inconsistent matching syntax. This should be inspecting the self.current_token instead of the next token.
-                Some(Token::ISNOTNULL) => {
-                    self.isnotnull()?;
-                    self.python_output.push_str(")\n");
-                    let code_gen = format!(
-                        "{} = {}[cond]\n",
-                        self.main_table_name, self.main_table_name
-                    );
-                    self.python_output.push_str(&code_gen);
-                    Ok(())
-                }
-                _ => {
-                    self.comparison()?;
-                    self.python_output.push_str(")\n");
-                    let code_gen = format!(
-                        "{} = {}[cond]\n",
-                        self.main_table_name, self.main_table_name
-                    );
-                    self.python_output.push_str(&code_gen);
-                    Ok(())
-                }
+        fn write_statement(&mut self) -> Result<Statement, ParseErr> {
+            let (format, options, path) = self.file_target(FileFormat::write_options)?;
+            match path {
+                Some(path) => Ok(Statement::Write {
+                    format,
+                    options,
+                    path,
+                }),
+                None => Err(ParseErr::CustomParseError {
+                    error_msg: "Expected the path of the file to write.".to_string(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
             }
         }
 
-        fn extend_statement(&mut self) -> Result<(), ParseErr> {
-            self.column()?;
-            self.match_token(&Token::EqualsOperator)?;
-            self.python_output.push_str(" = ");
-            self.expression()?;
-            self.python_output.push('\n');
-            Ok(())
+        /// Parses the `<format>[(<option> = <value>, ...)] ["<path>"]` file a statement reads from or writes to.
+        /// `options_of` gives the options allowed for the format.
+        fn file_target(
+            &mut self,
+            options_of: fn(&FileFormat) -> &'static [&'static str],
+        ) -> Result<(FileFormat, Vec<FormatOption>, Option<String>), ParseErr> {
+            match self.current() {
+                Some(tok) => match tok {
+                    Token::Identity(identity) => {
+                        let format = match identity.to_lowercase().as_str() {
+                            "csv" => FileFormat::Csv,
+                            "excel" => FileFormat::Excel,
+                            "parquet" => FileFormat::Parquet,
+                            "json" => FileFormat::Json,
+                            "jsonl" => FileFormat::JsonLines,
+                            "feather" => FileFormat::Feather,
+                            "sql" => {
+                                self.move_token();
+                                return self.sql_target(options_of);
+                            }
+                            _ => {
+                                return Err(ParseErr::CustomParseError {
+                                    error_msg: format!(
+                                        "Unknown file format. Expected one of: {}.",
+                                        FileFormat::NAMES.join(", ")
+                                    ),
+                                    location: self.location(),
+                                    source: Box::new(BaseErr {}),
+                                })
+                            }
+                        };
+                        self.move_token();
+                        let options = match self.current() {
+                            Some(Token::OpenBracket) => self.format_options(options_of(&format))?,
+                            _ => Vec::new(),
+                        };
+                        let path = match self.current() {
+                            Some(Token::StringLiteral(_)) => Some(self.str()?),
+                            _ => None,
+                        };
+                        Ok((format, options, path))
+                    }
+                    other => Err(ParseErr::WrongToken {
+                        expected: vec![Token::Identity("<file format>".to_string())],
+                        actual: other.clone(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    }),
+                },
+                None => Err(ParseErr::CustomParseError {
+                    error_msg: format!(
+                        "Unknown file format. Expected one of: {}.",
+                        FileFormat::NAMES.join(", ")
+                    ),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
         }
 
-        fn isnotnull(&mut self) -> Result<(), ParseErr> {
-            self.match_token(&Token::ISNOTNULL)?;
+        /// Parses the `("<table>", <connection>[, <option> = <value>, ...])` arguments of `sql`.
+        /// The name of the database table is returned as the path.
+        fn sql_target(
+            &mut self,
+            options_of: fn(&FileFormat) -> &'static [&'static str],
+        ) -> Result<(FileFormat, Vec<FormatOption>, Option<String>), ParseErr> {
             self.match_token(&Token::OpenBracket)?;
-            self.column()?;
-            self.match_token(&Token::CloseBracket)?;
-            self.python_output.push_str(".notna()");
-
-            Ok(())
-        }
-
-        // fn isnull(&mut self) -> Result<(), ParseErr> {
-        //     match self.current_token.take() {
-        //         Some(Token::ISNULL) => {
-        //             self.move_token();
-        //             match self.current_token.take() {
-        //                 Some(Token::OpenSquareBracket) => {
-        //                     self.move_token();
-        //                     self.column()?
-        //                 }
-        //                 _ => {
-        //                     return Err(ParseErr::CustomParseError {
-        //                         error_msg: "Expected '(' after the 'isnull' function.".to_string(),
-        //                         source: Box::new(BaseErr {}),
-        //                     })
-        //                 }
-        //             }
-        //             Ok(())
-        //         }
-        //         _ => Err(ParseErr::CustomParseError {
-        //             error_msg: "Expected isnull function.".to_string(),
-        //             source: Box::new(BaseErr {}),
-        //         }),
-        //     }
-        // }
-
-        fn comparison(&mut self) -> Result<(), ParseErr> {
-            // self.python_output.push_str("cond = (");
-            self.expression()?;
-            match self.current_token.as_ref() {
-                Some(Token::GreaterThan) => {
-                    self.python_output.push('>');
-                    self.move_token();
-                }
-                Some(Token::GreaterThanEqualsTo) => {
-                    self.python_output.push_str(">=");
-                    self.move_token();
-                }
-                Some(Token::LessThan) => {
-                    self.python_output.push('<');
-                    self.move_token();
-                }
-                Some(Token::LessThanEqualsTo) => {
-                    self.python_output.push_str("<=");
-                    self.move_token();
-                }
+            let table = self.str()?;
+            self.match_token(&Token::Comma)?;
+            let connection = match self.current() {
+                Some(Token::Identity(identity)) => identity.clone(),
                 Some(tok) => {
                     return Err(ParseErr::WrongToken {
-                        expected: vec![
-                            Token::GreaterThan,
-                            Token::GreaterThanEqualsTo,
-                            Token::LessThan,
-                            Token::LessThanEqualsTo,
-                        ],
+                        expected: vec![Token::Identity("<connection>".to_string())],
                         actual: tok.clone(),
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
                 None => {
                     return Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
+            };
+            self.move_token();
+
+            let format = FileFormat::Sql { connection };
+            let mut options = Vec::new();
+            if self.match_token(&Token::Comma).is_ok() {
+                self.option_list(options_of(&format), &mut options)?;
             }
-            self.expression()?;
-            // self.python_output.push_str(")");
-            Ok(())
+            self.match_token(&Token::CloseBracket)?;
+
+            Ok((format, options, Some(table)))
         }
 
-        fn expression(&mut self) -> Result<(), ParseErr> {
-            // self.python_output.push_str("(");
-            self.term()?;
+        /// Parses a bracketed list of `<name> = <value>` options.
+        fn format_options(&mut self, allowed: &[&str]) -> Result<Vec<FormatOption>, ParseErr> {
+            self.match_token(&Token::OpenBracket)?;
+            let mut options: Vec<FormatOption> = Vec::new();
+            self.option_list(allowed, &mut options)?;
+            self.match_token(&Token::CloseBracket)?;
+            Ok(options)
+        }
+
+        /// Parses comma-separated `<name> = <value>` options into `options`.
+        /// Only the names listed in `allowed` are accepted, and each may only be given once.
+        fn option_list(
+            &mut self,
+            allowed: &[&str],
+            options: &mut Vec<FormatOption>,
+        ) -> Result<(), ParseErr> {
             loop {
-                match self.current_token.as_ref() {
-                    Some(Token::PlusOperator) => {
-                        self.python_output.push('+');
-                        self.move_token();
-                        self.term()?;
+                let name = match self.current() {
+                    Some(Token::Identity(identity)) => identity.to_lowercase(),
+                    Some(tok) => {
+                        return Err(ParseErr::WrongToken {
+                            expected: vec![Token::Identity("<option name>".to_string())],
+                            actual: tok.clone(),
+                            location: self.location(),
+                            source: Box::new(BaseErr {}),
+                        })
                     }
-                    Some(Token::MinusOperator) => {
-                        self.python_output.push('-');
-                        self.move_token();
-                        self.term()?;
+                    None => {
+                        return Err(ParseErr::NoTokenLeftError {
+                            location: self.location(),
+                            source: Box::new(BaseErr {}),
+                        })
                     }
-                    _ => break,
+                };
+                if !allowed.contains(&name.as_str()) {
+                    let error_msg = match allowed.is_empty() {
+                        true => "This format does not take any options.".to_string(),
+                        false => format!(
+                            "Unknown option '{}'. Expected one of: {}.",
+                            name,
+                            allowed.join(", ")
+                        ),
+                    };
+                    return Err(ParseErr::CustomParseError {
+                        error_msg,
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    });
+                }
+                if options.iter().any(|option| option.name == name) {
+                    return Err(ParseErr::CustomParseError {
+                        error_msg: format!("Option '{}' is given more than once.", name),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    });
+                }
+                self.move_token();
+                self.match_token(&Token::EqualsOperator)?;
+                let value = self.literal()?;
+                options.push(FormatOption { name, value });
+
+                if self.match_token(&Token::Comma).is_err() {
+                    return Ok(());
                 }
             }
-            // self.python_output.push_str(")");
-            Ok(())
         }
 
-        fn term(&mut self) -> Result<(), ParseErr> {
-            // self.python_output.push_str("(");
-            self.unary()?;
+        fn where_statement(&mut self) -> Result<Statement, ParseErr> {
+            let condition = self.condition()?;
+            Ok(Statement::Where { condition })
+        }
+
+        fn extend_statement(&mut self) -> Result<Statement, ParseErr> {
+            let column = self.column()?;
+            self.match_token(&Token::EqualsOperator)?;
+            let value = self.condition()?;
+            Ok(Statement::Extend { column, value })
+        }
+
+        fn project_statement(&mut self) -> Result<Statement, ParseErr> {
+            let mut columns = Vec::new();
             loop {
-                match self.current_token.as_ref() {
-                    Some(Token::MulOperator) => {
-                        self.python_output.push('*');
-                        self.move_token();
-                        self.unary()?;
-                    }
-                    Some(Token::DivOperator) => {
-                        self.python_output.push('/');
-                        self.move_token();
-                        self.unary()?;
-                    }
-                    _ => break,
+                let column = self.column()?;
+                let value = match self.match_token(&Token::EqualsOperator) {
+                    Ok(()) => Some(self.condition()?),
+                    Err(_) => None,
+                };
+                columns.push(ProjectColumn { column, value });
+
+                if self.match_token(&Token::Comma).is_err() {
+                    return Ok(Statement::Project { columns });
                 }
             }
-
-            // self.python_output.push_str(")");
-            Ok(())
         }
 
-        fn unary(&mut self) -> Result<(), ParseErr> {
-            if self.match_token(&Token::PlusOperator).is_ok() {
-                self.primary()?;
-            }
+        fn project_rename_statement(&mut self) -> Result<Statement, ParseErr> {
+            let mut renames = Vec::new();
+            loop {
+                let new = self.column()?;
+                self.match_token(&Token::EqualsOperator)?;
+                let old = self.column()?;
+                renames.push(Rename { new, old });
 
-            if self.match_token(&Token::MinusOperator).is_ok() {
-                self.python_output.push_str("-1 * ");
-                self.primary()?;
+                if self.match_token(&Token::Comma).is_err() {
+                    return Ok(Statement::ProjectRename { renames });
+                }
             }
+        }
 
-            self.primary()
+        fn sort_statement(&mut self) -> Result<Statement, ParseErr> {
+            let keys = self.sort_keys()?;
+            Ok(Statement::Sort { keys })
         }
 
-        fn primary(&mut self) -> Result<(), ParseErr> {
-            match &self.current_token {
-                Some(Token::OpenSquareBracket) => {
-                    self.column()?;
-                    Ok(())
+        fn distinct_statement(&mut self) -> Result<Statement, ParseErr> {
+            let columns = match self.match_token(&Token::MulOperator) {
+                Ok(()) => Vec::new(),
+                Err(_) => self.column_list()?,
+            };
+            Ok(Statement::Distinct { columns })
+        }
+
+        fn summarize_statement(&mut self) -> Result<Statement, ParseErr> {
+            let mut aggregations: Vec<Aggregation> = Vec::new();
+            let mut named: Vec<bool> = Vec::new();
+            let mut locations: Vec<Location> = Vec::new();
+            loop {
+                let location = self.location();
+                let (aggregation, is_named) = self.aggregation()?;
+                let duplicate = aggregations
+                    .iter()
+                    .zip(named.iter())
+                    .any(|(a, &a_named)| a_named && a.name == aggregation.name);
+                if is_named && duplicate {
+                    return Err(ParseErr::CustomParseError {
+                        error_msg: format!(
+                            "Column '{}' is aggregated more than once.",
+                            aggregation.name.name
+                        ),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    });
                 }
-                Some(Token::Integer(_)) => {
-                    self.number()?;
-                    Ok(())
+                aggregations.push(aggregation);
+                named.push(is_named);
+                locations.push(location);
+
+                if self.match_token(&Token::Comma).is_err() {
+                    break;
                 }
-                Some(Token::Float(_)) => {
-                    self.float()?;
-                    Ok(())
+            }
+
+            let by = match self.check_token(&Token::BY) {
+                true => {
+                    self.move_token();
+                    self.column_list()?
                 }
-                Some(tok) => Err(ParseErr::WrongToken {
-                    expected: vec![Token::OpenSquareBracket],
-                    actual: tok.clone(),
-                    source: Box::new(BaseErr {}),
-                }),
-                _ => Err(ParseErr::CustomParseError {
-                    error_msg: "Expected a column, number or float.".to_string(),
+                false => Vec::new(),
+            };
+
+            // An explicit name would overwrite the BY column of the same name.
+            let clash = aggregations
+                .iter()
+                .zip(named.iter())
+                .zip(locations)
+                .find(|((a, &is_named), _)| is_named && by.contains(&a.name));
+            if let Some(((aggregation, _), location)) = clash {
+                return Err(ParseErr::CustomParseError {
+                    error_msg: format!(
+                        "Column '{}' is both aggregated and grouped by.",
+                        aggregation.name.name
+                    ),
+                    location,
                     source: Box::new(BaseErr {}),
-                }),
+                });
             }
-        }
 
-        fn column(&mut self) -> Result<(), ParseErr> {
-            if self.match_token(&Token::OpenSquareBracket).is_ok() {
-                self.python_output.push_str("df.loc[:,");
-                self.str()?;
-                self.match_token(&Token::CloseSquareBracket)?;
-                self.python_output.push(']');
-                return Ok(());
+            // Generated names clashing with another column are numbered, e.g. `avg_1`.
+            let mut taken: HashSet<String> = aggregations
+                .iter()
+                .zip(named.iter())
+                .filter(|(_, &is_named)| is_named)
+                .map(|(a, _)| a.name.name.clone())
+                .chain(by.iter().map(|column| column.name.clone()))
+                .collect();
+            for (aggregation, _) in aggregations
+                .iter_mut()
+                .zip(named.iter())
+                .filter(|(_, &is_named)| !is_named)
+            {
+                let base = aggregation.name.name.clone();
+                let mut suffix = 1;
+                while taken.contains(&aggregation.name.name) {
+                    aggregation.name.name = format!("{}{}", base, suffix);
+                    suffix += 1;
+                }
+                taken.insert(aggregation.name.name.clone());
             }
+            Ok(Statement::Summarize { aggregations, by })
+        }
 
-            match self.current_token.as_ref() {
-                Some(Token::Identity(_identity)) => {
-                    self.move_token();
-                    todo!();
-                    Ok(())
+        /// Parses `[<column> =] <aggregate>(<arguments>)`, and returns whether the column was given.
+        /// Without a column, the result is named after the aggregate and its column, e.g. `sum_Hours`.
+        fn aggregation(&mut self) -> Result<(Aggregation, bool), ParseErr> {
+            let name = match self.check_next_token(&Token::OpenBracket) {
+                true => None,
+                false => {
+                    let name = self.column()?;
+                    self.match_token(&Token::EqualsOperator)?;
+                    Some(name)
                 }
+            };
+
+            let function_name = match self.current() {
+                Some(Token::Identity(identity)) => identity.to_lowercase(),
                 Some(tok) => {
-                    Err(ParseErr::WrongToken {
-                        expected: vec![
-                            Token::OpenSquareBracket,
-                            Token::Identity("Identity".to_string()),
-                        ],
+                    return Err(ParseErr::WrongToken {
+                        expected: vec![Token::Identity("<aggregate function>".to_string())],
                         actual: tok.clone(),
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
                 None => {
-                    Err(ParseErr::NoTokenLeftError {
+                    return Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
-            }
-        }
-
-        fn number(&mut self) -> Result<(), ParseErr> {
-            match self.current_token.as_ref() {
-                Some(Token::Integer(int)) => {
-                    let code_gen = format!("{}", int);
-                    self.python_output.push_str(&code_gen);
-                    self.move_token();
-                    Ok(())
-                }
-                Some(tok) => {
-                    Err(ParseErr::WrongToken {
-                        expected: vec![Token::Integer(0)],
-                        actual: tok.clone(),
+            };
+            let function = match function_name.as_str() {
+                "count" => AggregateFunction::Count,
+                "sum" => AggregateFunction::Sum,
+                "avg" | "mean" => AggregateFunction::Avg,
+                "min" => AggregateFunction::Min,
+                "max" => AggregateFunction::Max,
+                "median" => AggregateFunction::Median,
+                "stdev" => AggregateFunction::Stdev,
+                "variance" => AggregateFunction::Variance,
+                "dcount" => AggregateFunction::DCount,
+                "countif" => AggregateFunction::CountIf,
+                // The percentile itself is parsed with the arguments.
+                "percentile" => AggregateFunction::Percentile(0.0),
+                _ => {
+                    return Err(ParseErr::CustomParseError {
+                        error_msg: format!(
+                            "Unknown aggregate function. Expected one of: {}.",
+                            AggregateFunction::NAMES.join(", ")
+                        ),
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
+            };
+            self.move_token();
+            self.match_token(&Token::OpenBracket)?;
+
+            let (function, argument) = match function {
+                AggregateFunction::Count if self.check_token(&Token::CloseBracket) => {
+                    (function, None)
+                }
+                AggregateFunction::Percentile(_) => {
+                    let argument = self.condition()?;
+                    self.match_token(&Token::Comma)?;
+                    let percentile = match self.literal()? {
+                        Literal::Integer(int) => int as f64,
+                        Literal::Float(float) => float,
+                        _ => {
+                            return Err(ParseErr::CustomParseError {
+                                error_msg: "Expected a percentile between 0 and 100.".to_string(),
+                                location: self.location(),
+                                source: Box::new(BaseErr {}),
+                            })
+                        }
+                    };
+                    if percentile > 100.0 {
+                        return Err(ParseErr::CustomParseError {
+                            error_msg: "Expected a percentile between 0 and 100.".to_string(),
+                            location: self.location(),
+                            source: Box::new(BaseErr {}),
+                        });
+                    }
+                    (AggregateFunction::Percentile(percentile), Some(argument))
+                }
+                _ => (function, Some(self.condition()?)),
+            };
+            self.match_token(&Token::CloseBracket)?;
+
+            let is_named = name.is_some();
+            let name = match name {
+                Some(name) => name,
                 None => {
-                    Err(ParseErr::NoTokenLeftError {
-                        source: Box::new(BaseErr {}),
-                    })
+                    let column = match &argument {
+                        Some(Expression::Column(column)) => column.name.as_str(),
+                        _ => "",
+                    };
+                    let name = match function {
+                        AggregateFunction::Percentile(percentile) => format!(
+                            "{}_{}_{}",
+                            function_name,
+                            column,
+                            percentile.to_string().replace('.', "_")
+                        ),
+                        _ => format!("{}_{}", function_name, column),
+                    };
+                    Column { name }
                 }
-            }
+            };
+
+            Ok((
+                Aggregation {
+                    name,
+                    function,
+                    argument,
+                },
+                is_named,
+            ))
         }
 
-        fn float(&mut self) -> Result<(), ParseErr> {
-            match self.current_token.as_ref() {
-                Some(Token::Float(float)) => {
-                    let code_gen = format!("{}", float);
-                    self.python_output.push_str(&code_gen);
-                    self.move_token();
-                    Ok(())
-                }
+        fn take_statement(&mut self) -> Result<Statement, ParseErr> {
+            let count = self.count()?;
+            Ok(Statement::Take { count })
+        }
+
+        fn top_statement(&mut self) -> Result<Statement, ParseErr> {
+            let count = self.count()?;
+            let keys = self.sort_keys()?;
+            Ok(Statement::Top { count, keys })
+        }
+
+        fn sample_statement(&mut self) -> Result<Statement, ParseErr> {
+            let count = self.count()?;
+            let seed = match self.keyword() {
+                Some("seed") => {
+                    self.move_token();
+                    self.match_token(&Token::EqualsOperator)?;
+                    Some(self.count()?)
+                }
+                _ => None,
+            };
+            Ok(Statement::Sample { count, seed })
+        }
+
+        fn join_statement(&mut self) -> Result<Statement, ParseErr> {
+            let kind = match self.keyword() {
+                Some("kind") => {
+                    self.move_token();
+                    let kind = self
+                        .match_token(&Token::EqualsOperator)
+                        .and_then(|_| self.join_kind());
+                    if kind.is_err() {
+                        // Otherwise recovery would resume at the first statement of the joined query.
+                        self.skip_subquery();
+                    }
+                    kind?
+                }
+                _ => JoinKind::Inner,
+            };
+
+            self.match_token(&Token::OpenBracket)?;
+            let right = self.subquery()?;
+            self.match_token(&Token::CloseBracket)?;
+
+            self.match_token(&Token::ON)?;
+            let mut on = vec![self.join_key()?];
+            while self.match_token(&Token::Comma).is_ok() {
+                on.push(self.join_key()?);
+            }
+
+            Ok(Statement::Join { kind, right, on })
+        }
+
+        fn join_kind(&mut self) -> Result<JoinKind, ParseErr> {
+            let kind = match self.current() {
+                Some(Token::Identity(identity)) => match identity.to_lowercase().as_str() {
+                    "inner" => Some(JoinKind::Inner),
+                    "leftouter" => Some(JoinKind::LeftOuter),
+                    "rightouter" => Some(JoinKind::RightOuter),
+                    "fullouter" => Some(JoinKind::FullOuter),
+                    "leftanti" => Some(JoinKind::LeftAnti),
+                    "leftsemi" => Some(JoinKind::LeftSemi),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match kind {
+                Some(kind) => {
+                    self.move_token();
+                    Ok(kind)
+                }
+                None => Err(ParseErr::CustomParseError {
+                    error_msg: format!(
+                        "Unknown join kind. Expected one of: {}.",
+                        JoinKind::NAMES.join(", ")
+                    ),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
+        }
+
+        /// Parses the query building the right table of a join, up to its closing bracket.
+        /// Each table may only appear once in the query, since its variable holds its current state.
+        fn subquery(&mut self) -> Result<Program, ParseErr> {
+            let result = self.subquery_program();
+            if result.is_err() {
+                self.skip_subquery();
+            }
+            result
+        }
+
+        /// Skips the rest of a join after a syntax error in its query, up to the next statement.
+        /// This keeps the statements of the bracketed query from being parsed as statements of the main query.
+        fn skip_subquery(&mut self) {
+            // Only the bracket closing a joined query is followed by ON.
+            while self.current().is_some()
+                && !(self.check_token(&Token::CloseBracket) && self.check_next_token(&Token::ON))
+            {
+                self.move_token();
+            }
+            self.move_token();
+            while let Some(tok) = self.current() {
+                if tok.is_statement() || tok == &Token::CloseBracket {
+                    break;
+                }
+                self.move_token();
+            }
+        }
+
+        fn subquery_program(&mut self) -> Result<Program, ParseErr> {
+            if let Some(Token::Identity(identity)) = self.current() {
+                if self.entities.contains(&EntityType::Table(identity.clone())) {
+                    return Err(ParseErr::CustomParseError {
+                        error_msg: format!("Table '{}' is already used in this query.", identity),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    });
+                }
+            }
+            let table = self.table()?;
+            self.starts_with_read()?;
+
+            let mut statements = Vec::new();
+            while !self.check_token(&Token::CloseBracket) {
+                statements.push(self.statement(statements.is_empty())?);
+            }
+            Ok(Program { table, statements })
+        }
+
+        /// Parses `<column>` or `$left.<column> == $right.<column>`, in either order.
+        fn join_key(&mut self) -> Result<JoinKey, ParseErr> {
+            if !self.check_token(&Token::LeftTable) && !self.check_token(&Token::RightTable) {
+                let column = self.column()?;
+                return Ok(JoinKey {
+                    left: column.clone(),
+                    right: column,
+                });
+            }
+
+            let (first_is_left, first) = self.side_column()?;
+            self.match_token(&Token::EqualsTo)?;
+            let (second_is_left, second) = self.side_column()?;
+            match (first_is_left, second_is_left) {
+                (true, false) => Ok(JoinKey {
+                    left: first,
+                    right: second,
+                }),
+                (false, true) => Ok(JoinKey {
+                    left: second,
+                    right: first,
+                }),
+                _ => Err(ParseErr::CustomParseError {
+                    error_msg: "Expected one '$left' and one '$right' column.".to_string(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
+        }
+
+        /// Parses `$left.<column>` or `$right.<column>`, returning whether the column is from the left table.
+        fn side_column(&mut self) -> Result<(bool, Column), ParseErr> {
+            let is_left = match self.current() {
+                Some(Token::LeftTable) => true,
+                Some(Token::RightTable) => false,
+                Some(tok) => {
+                    return Err(ParseErr::WrongToken {
+                        expected: vec![Token::LeftTable, Token::RightTable],
+                        actual: tok.clone(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+                None => {
+                    return Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+            };
+            self.move_token();
+            self.match_token(&Token::Dot)?;
+            Ok((is_left, self.column()?))
+        }
+
+        /// Parses `BY <sort key>, ...`.
+        fn sort_keys(&mut self) -> Result<Vec<SortKey>, ParseErr> {
+            self.match_token(&Token::BY)?;
+
+            let mut keys = vec![self.sort_key()?];
+            while self.match_token(&Token::Comma).is_ok() {
+                keys.push(self.sort_key()?);
+            }
+
+            // pandas places the nulls of all sort columns the same way.
+            let mut placements = keys.iter().filter_map(|key| key.nulls);
+            if let Some(first) = placements.next() {
+                if placements.any(|nulls| nulls != first) {
+                    return Err(ParseErr::CustomParseError {
+                        error_msg: "All sort columns must place nulls the same way.".to_string(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    });
+                }
+            }
+            Ok(keys)
+        }
+
+        /// Parses `<column> [asc | desc] [nulls first | nulls last]`.
+        /// The direction and null placement words are only keywords in this position, so they remain valid column names.
+        fn sort_key(&mut self) -> Result<SortKey, ParseErr> {
+            let column = self.column()?;
+
+            let ascending = match self.keyword() {
+                Some("asc") => {
+                    self.move_token();
+                    true
+                }
+                Some("desc") => {
+                    self.move_token();
+                    false
+                }
+                _ => false,
+            };
+
+            let nulls = match self.keyword() {
+                Some("nulls") => {
+                    self.move_token();
+                    let nulls = match self.keyword() {
+                        Some("first") => NullsPosition::First,
+                        Some("last") => NullsPosition::Last,
+                        _ => {
+                            return Err(ParseErr::CustomParseError {
+                                error_msg: "Expected 'first' or 'last' after 'nulls'.".to_string(),
+                                location: self.location(),
+                                source: Box::new(BaseErr {}),
+                            })
+                        }
+                    };
+                    self.move_token();
+                    Some(nulls)
+                }
+                _ => None,
+            };
+
+            Ok(SortKey {
+                column,
+                ascending,
+                nulls,
+            })
+        }
+
+        /// Returns the current identifier in lowercase, for keywords which are only reserved in some positions.
+        fn keyword(&self) -> Option<&'static str> {
+            const KEYWORDS: &[&str] = &["asc", "desc", "nulls", "first", "last", "seed", "kind"];
+            match self.current() {
+                Some(Token::Identity(identity)) => KEYWORDS
+                    .iter()
+                    .find(|keyword| identity.eq_ignore_ascii_case(keyword))
+                    .copied(),
+                _ => None,
+            }
+        }
+
+        /// Parses a comma-separated list of columns.
+        fn column_list(&mut self) -> Result<Vec<Column>, ParseErr> {
+            let mut columns = vec![self.column()?];
+            while self.match_token(&Token::Comma).is_ok() {
+                columns.push(self.column()?);
+            }
+            Ok(columns)
+        }
+
+        fn isnotnull(&mut self) -> Result<Expression, ParseErr> {
+            self.match_token(&Token::ISNOTNULL)?;
+            self.match_token(&Token::OpenBracket)?;
+            let column = self.column()?;
+            self.match_token(&Token::CloseBracket)?;
+
+            Ok(Expression::IsNotNull(column))
+        }
+
+        fn isnull(&mut self) -> Result<Expression, ParseErr> {
+            self.match_token(&Token::ISNULL)?;
+            self.match_token(&Token::OpenBracket)?;
+            let column = self.column()?;
+            self.match_token(&Token::CloseBracket)?;
+
+            Ok(Expression::IsNull(column))
+        }
+
+        /// Parses conditions chained with OR, the loosest binding operator.
+        fn condition(&mut self) -> Result<Expression, ParseErr> {
+            let mut left = self.conjunction()?;
+            while self.match_token(&Token::OR).is_ok() {
+                let right = self.conjunction()?;
+                left = Expression::Binary {
+                    operator: BinaryOperator::Or,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                };
+            }
+            Ok(left)
+        }
+
+        /// Parses conditions chained with AND.
+        fn conjunction(&mut self) -> Result<Expression, ParseErr> {
+            let mut left = self.negation()?;
+            while self.match_token(&Token::AND).is_ok() {
+                let right = self.negation()?;
+                left = Expression::Binary {
+                    operator: BinaryOperator::And,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                };
+            }
+            Ok(left)
+        }
+
+        fn negation(&mut self) -> Result<Expression, ParseErr> {
+            if self.match_token(&Token::NOT).is_ok() {
+                let operand = self.negation()?;
+                return Ok(Expression::Unary {
+                    operator: UnaryOperator::Not,
+                    operand: Box::new(operand),
+                });
+            }
+            self.comparison()
+        }
+
+        /// Parses an expression, optionally compared against a second expression.
+        /// Comparisons cannot be chained, i.e. `a < b < c` is not allowed.
+        fn comparison(&mut self) -> Result<Expression, ParseErr> {
+            let left = self.expression()?;
+            let operator = match self.current() {
+                Some(Token::GreaterThan) => BinaryOperator::GreaterThan,
+                Some(Token::GreaterThanEqualsTo) => BinaryOperator::GreaterThanEqualsTo,
+                Some(Token::LessThan) => BinaryOperator::LessThan,
+                Some(Token::LessThanEqualsTo) => BinaryOperator::LessThanEqualsTo,
+                Some(Token::EqualsTo) => BinaryOperator::EqualsTo,
+                Some(Token::NotEqualsTo) => BinaryOperator::NotEqualsTo,
+                Some(Token::EqualsOperator) => {
+                    return Err(ParseErr::CustomParseError {
+                        error_msg: "Use '==' to compare values. '=' is only used for assignments."
+                            .to_string(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+                _ => return Ok(left),
+            };
+            self.move_token();
+            let right = self.expression()?;
+            Ok(Expression::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+        }
+
+        fn expression(&mut self) -> Result<Expression, ParseErr> {
+            let mut left = self.term()?;
+            loop {
+                let operator = match self.current() {
+                    Some(Token::PlusOperator) => BinaryOperator::Add,
+                    Some(Token::MinusOperator) => BinaryOperator::Subtract,
+                    _ => break,
+                };
+                self.move_token();
+                let right = self.term()?;
+                left = Expression::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                };
+            }
+            Ok(left)
+        }
+
+        fn term(&mut self) -> Result<Expression, ParseErr> {
+            let mut left = self.unary()?;
+            loop {
+                let operator = match self.current() {
+                    Some(Token::MulOperator) => BinaryOperator::Multiply,
+                    Some(Token::DivOperator) => BinaryOperator::Divide,
+                    _ => break,
+                };
+                self.move_token();
+                let right = self.unary()?;
+                left = Expression::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                };
+            }
+            Ok(left)
+        }
+
+        fn unary(&mut self) -> Result<Expression, ParseErr> {
+            let operator = match self.current() {
+                Some(Token::PlusOperator) => UnaryOperator::Plus,
+                Some(Token::MinusOperator) => UnaryOperator::Minus,
+                _ => return self.primary(),
+            };
+            self.move_token();
+            let operand = self.primary()?;
+            Ok(Expression::Unary {
+                operator,
+                operand: Box::new(operand),
+            })
+        }
+
+        fn primary(&mut self) -> Result<Expression, ParseErr> {
+            match self.current() {
+                Some(Token::OpenSquareBracket) | Some(Token::Identity(_)) => {
+                    Ok(Expression::Column(self.column()?))
+                }
+                Some(Token::Integer(_)) => Ok(Expression::Literal(self.number()?)),
+                Some(Token::Float(_)) => Ok(Expression::Literal(self.float()?)),
+                Some(Token::StringLiteral(_)) | Some(Token::Boolean(_)) => {
+                    Ok(Expression::Literal(self.literal()?))
+                }
+                Some(Token::ISNOTNULL) => self.isnotnull(),
+                Some(Token::ISNULL) => self.isnull(),
+                Some(Token::Error) if self.integer_out_of_range() => Err(self.out_of_range()),
+                Some(Token::OpenBracket) => {
+                    self.move_token();
+                    let condition = self.condition()?;
+                    self.match_token(&Token::CloseBracket)?;
+                    Ok(condition)
+                }
+                Some(tok) => Err(ParseErr::WrongToken {
+                    expected: vec![
+                        Token::OpenSquareBracket,
+                        Token::Identity("<column name>".to_string()),
+                        Token::Integer(0),
+                        Token::Float(0.0),
+                        Token::StringLiteral("".to_string()),
+                        Token::Boolean(false),
+                        Token::ISNOTNULL,
+                        Token::ISNULL,
+                        Token::OpenBracket,
+                    ],
+                    actual: tok.clone(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+                _ => Err(ParseErr::CustomParseError {
+                    error_msg: "Expected a column, literal, function or bracketed condition."
+                        .to_string(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
+        }
+
+        fn column(&mut self) -> Result<Column, ParseErr> {
+            if self.match_token(&Token::OpenSquareBracket).is_ok() {
+                let name = self.str()?;
+                self.match_token(&Token::CloseSquareBracket)?;
+                return Ok(Column { name });
+            }
+
+            match self.current() {
+                Some(Token::Identity(identity)) => {
+                    let name = identity.clone();
+                    self.move_token();
+                    Ok(Column { name })
+                }
+                Some(tok) => {
+                    Err(ParseErr::WrongToken {
+                        expected: vec![
+                            Token::OpenSquareBracket,
+                            Token::Identity("Identity".to_string()),
+                        ],
+                        actual: tok.clone(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+                None => {
+                    Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+            }
+        }
+
+        /// Whether the current token is a run of digits too large to lex as an integer.
+        fn integer_out_of_range(&self) -> bool {
+            match self.current_token.as_ref() {
+                Some(spanned) if spanned.token == Token::Error => self
+                    .lexer
+                    .source()
+                    .get(spanned.span.clone())
+                    .is_some_and(|slice| slice.bytes().all(|b| b.is_ascii_digit())),
+                _ => false,
+            }
+        }
+
+        fn out_of_range(&self) -> ParseErr {
+            ParseErr::CustomParseError {
+                error_msg: "integer literal out of range".to_string(),
+                location: self.location(),
+                source: Box::new(BaseErr {}),
+            }
+        }
+
+        /// Parses a non-negative integer, such as a number of rows.
+        fn count(&mut self) -> Result<u32, ParseErr> {
+            match self.current() {
+                Some(Token::Integer(int)) => {
+                    let count = u32::try_from(*int).map_err(|_| ParseErr::CustomParseError {
+                        error_msg: format!("Expected a count no greater than {}.", u32::MAX),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })?;
+                    self.move_token();
+                    Ok(count)
+                }
+                Some(tok) => Err(ParseErr::WrongToken {
+                    expected: vec![Token::Integer(0)],
+                    actual: tok.clone(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+                None => Err(ParseErr::NoTokenLeftError {
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
+        }
+
+        fn literal(&mut self) -> Result<Literal, ParseErr> {
+            match self.current() {
+                Some(Token::Integer(_)) => self.number(),
+                Some(Token::Float(_)) => self.float(),
+                Some(Token::StringLiteral(_)) => Ok(Literal::String(self.str()?)),
+                Some(Token::Boolean(boolean)) => {
+                    let literal = Literal::Boolean(*boolean);
+                    self.move_token();
+                    Ok(literal)
+                }
+                Some(Token::Error) if self.integer_out_of_range() => Err(self.out_of_range()),
+                Some(tok) => Err(ParseErr::WrongToken {
+                    expected: vec![
+                        Token::Integer(0),
+                        Token::Float(0.0),
+                        Token::StringLiteral("".to_string()),
+                        Token::Boolean(false),
+                    ],
+                    actual: tok.clone(),
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+                None => Err(ParseErr::NoTokenLeftError {
+                    location: self.location(),
+                    source: Box::new(BaseErr {}),
+                }),
+            }
+        }
+
+        fn number(&mut self) -> Result<Literal, ParseErr> {
+            match self.current() {
+                Some(Token::Integer(int)) => {
+                    let literal = Literal::Integer(*int);
+                    self.move_token();
+                    Ok(literal)
+                }
+                Some(tok) => {
+                    Err(ParseErr::WrongToken {
+                        expected: vec![Token::Integer(0)],
+                        actual: tok.clone(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+                None => {
+                    Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+            }
+        }
+
+        fn float(&mut self) -> Result<Literal, ParseErr> {
+            match self.current() {
+                Some(Token::Float(float)) => {
+                    let literal = Literal::Float(*float);
+                    self.move_token();
+                    Ok(literal)
+                }
                 Some(tok) => {
                     Err(ParseErr::WrongToken {
                         expected: vec![Token::Float(0.0)],
                         actual: tok.clone(),
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+                None => {
+                    Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
+                        source: Box::new(BaseErr {}),
+                    })
+                }
+            }
+        }
+
+        fn str(&mut self) -> Result<String, ParseErr> {
+            match self.current() {
+                Some(Token::StringLiteral(string)) => {
+                    let string = string.clone();
+                    self.move_token();
+                    Ok(string)
+                }
+                Some(tok) => {
+                    Err(ParseErr::WrongToken {
+                        expected: vec![Token::StringLiteral("".to_string())],
+                        actual: tok.clone(),
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
                 None => {
                     Err(ParseErr::NoTokenLeftError {
+                        location: self.location(),
                         source: Box::new(BaseErr {}),
                     })
                 }
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::parser::RustyParser;
+    use crate::ast::ast::{
+        BinaryOperator, Column, Expression, FileFormat, Literal, Program, Statement,
+    };
+    use crate::codegen::codegen::PythonCodeGen;
+
+    /// Extremely basic test to see if the parsing even works.
+    /// If this fails, this means that there are serious underlying problems that needs to be fixed even before addressing any other failed tests.
+    #[test]
+    fn basic_parsing_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE ["foo bar"] > 5
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (sourceTable.loc[:,\"foo bar\"]>5)\nsourceTable = sourceTable[cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+        println!("{}", &python_output);
+    }
+
+    #[test]
+    fn isnotnull_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE isnotnull(["foo bar baz"])
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (sourceTable.loc[:,\"foo bar baz\"].notna())\nsourceTable = sourceTable[cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+        println!("{}", &python_output);
+    }
+
+    #[test]
+    fn extend_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | EXTEND ["foo"] = ["bar"] * 2
+        | EXTEND ["baz"] = ["qux"] * 5.1
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \nsourceTable.loc[:,\"foo\"] = sourceTable.loc[:,\"bar\"]*2\nsourceTable.loc[:,\"baz\"] = sourceTable.loc[:,\"qux\"]*5.1\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+        println!("{}", &python_output);
+    }
+
+    /// The parser output should be inspectable without going through the code generator.
+    #[test]
+    fn ast_test() {
+        let input = r#"
+        sourceTable
+        | READ excel
+        | WHERE ["foo"] + 1 * 2 <= -3
+        "#;
+
+        let expected_ast = Program {
+            table: "sourceTable".to_string(),
+            statements: vec![
+                Statement::Read {
+                    format: FileFormat::Excel,
+                    options: Vec::new(),
+                    path: None,
+                },
+                Statement::Where {
+                    condition: Expression::Binary {
+                        operator: BinaryOperator::LessThanEqualsTo,
+                        left: Box::new(Expression::Binary {
+                            operator: BinaryOperator::Add,
+                            left: Box::new(Expression::Column(Column {
+                                name: "foo".to_string(),
+                            })),
+                            right: Box::new(Expression::Binary {
+                                operator: BinaryOperator::Multiply,
+                                left: Box::new(Expression::Literal(Literal::Integer(1))),
+                                right: Box::new(Expression::Literal(Literal::Integer(2))),
+                            }),
+                        }),
+                        right: Box::new(Expression::Unary {
+                            operator: crate::ast::ast::UnaryOperator::Minus,
+                            operand: Box::new(Expression::Literal(Literal::Integer(3))),
+                        }),
+                    },
+                },
+            ],
+        };
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        assert_eq!(expected_ast, program);
+    }
+
+    #[test]
+    fn error_location_test() {
+        let input = "sourceTable\n| READ csv\n| WHERE [\"foo\"] > >";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        let err = &errors[0];
+        let location = err.location();
+        assert_eq!((location.line, location.column), (3, 19));
+        assert_eq!(location.span, 41..42);
+        assert!(err.to_string().starts_with("line 3, column 19: "));
+    }
+
+    /// Every broken statement is reported, and the statements around them are still parsed.
+    #[test]
+    fn error_recovery_test() {
+        let input = r#"
+        sourceTable
+        | READ foo
+        | WHERE ["foo"] > 1
+        | EXTEND ["bar"] 2
+        | WHERE ["baz" < 3
+        | EXTEND ["qux"] = 4
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        let lines: Vec<usize> = errors.iter().map(|err| err.location().line).collect();
+        assert_eq!(vec![3, 5, 6], lines);
+
+        // Recovery from an error in a joined query skips the rest of the join.
+        let input = r#"
+        sourceTable
+        | READ csv
+        | JOIN (sites | READ foo | WHERE a > 1 | JOIN (owners | READ csv) ON b) ON k
+        | WHERE c > 1
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(1, errors.len());
+    }
+
+    #[test]
+    fn identity_column_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE OperationalHours > 10
+        | EXTEND EquipmentType = EquipmentLabel
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (sourceTable.loc[:,\"OperationalHours\"]>10)\nsourceTable = sourceTable[cond]\nsourceTable.loc[:,\"EquipmentType\"] = sourceTable.loc[:,\"EquipmentLabel\"]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    /// Unsupported input must be reported as an error rather than panicking.
+    #[test]
+    fn unsupported_read_test() {
+        let input = r#"
+        sourceTable
+        | READ 5
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(1, errors.len());
+    }
+
+    #[test]
+    fn boolean_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE a > 1 AND NOT (b < 2 OR isnotnull(c)) or d >= 3 and e <= 4
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (((sourceTable.loc[:,\"a\"]>1) & ~((sourceTable.loc[:,\"b\"]<2) | sourceTable.loc[:,\"c\"].notna())) | ((sourceTable.loc[:,\"d\"]>=3) & (sourceTable.loc[:,\"e\"]<=4)))\nsourceTable = sourceTable[cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn float_precision_test() {
+        let input = r#"
+        sourceTable
+        | READ csv "data.csv"
+        | EXTEND a = 0.1 + 16777217.5
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = pd.read_csv(\"data.csv\") \nsourceTable.loc[:,\"a\"] = 0.1+16777217.5\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new().generate(&program).unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn integer_range_test() {
+        let input = r#"
+        sourceTable
+        | READ csv "data.csv"
+        | WHERE a > 99999999999
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = pd.read_csv(\"data.csv\") \ncond = (sourceTable.loc[:,\"a\"]>99999999999)\nsourceTable = sourceTable[cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new().generate(&program).unwrap();
+        assert_eq!(expected_output, &python_output);
+
+        let input = r#"
+        sourceTable
+        | READ csv "data.csv"
+        | WHERE a > 99999999999999999999
+        | TAKE 99999999999
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(2, errors.len());
+        assert_eq!("integer literal out of range", errors[0].message());
+        assert_eq!(4, errors[0].location().line);
+        assert_eq!(
+            "Expected a count no greater than 4294967295.",
+            errors[1].message()
+        );
+    }
+
+    #[test]
+    fn bracketed_expression_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | EXTEND ["foo"] = -(bar + 1) * 2
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \nsourceTable.loc[:,\"foo\"] = -1 * (sourceTable.loc[:,\"bar\"]+1)*2\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn equality_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE ["foo"] == 1 AND bar != 2.5 AND baz <> qux
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = ((sourceTable.loc[:,\"foo\"]==1) & (sourceTable.loc[:,\"bar\"]!=2.5) & (sourceTable.loc[:,\"baz\"]!=sourceTable.loc[:,\"qux\"]))\nsourceTable = sourceTable[cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn single_equals_comparison_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE ["foo"] = 1
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert!(errors[0].message().contains("'=='"));
+    }
+
+    #[test]
+    fn string_literal_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE Manufacturer == "Foo Corp"
+        | EXTEND Label = "pump"
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (sourceTable.loc[:,\"Manufacturer\"]==\"Foo Corp\")\nsourceTable = sourceTable[cond]\nsourceTable.loc[:,\"Label\"] = \"pump\"\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    /// Column names and values must be kept exactly as written, including punctuation and repeated spaces.
+    #[test]
+    fn exact_string_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE ["Operational  Hours (h)"] > 1 AND ID == 'Pump-01'
+        | EXTEND Pump_Label = "it's \"on\""
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = ((sourceTable.loc[:,\"Operational  Hours (h)\"]>1) & (sourceTable.loc[:,\"ID\"]==\"Pump-01\"))\nsourceTable = sourceTable[cond]\nsourceTable.loc[:,\"Pump_Label\"] = \"it's \\\"on\\\"\"\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn isnull_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | WHERE isnull(["foo bar"]) OR NOT isnull(baz) AND baz > 1
+        | EXTEND missing = isnull(qux)
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = <filepath> \nsourceTable = pd.read_csv(sourceTable) \ncond = (sourceTable.loc[:,\"foo bar\"].isna() | (~(sourceTable.loc[:,\"baz\"].isna()) & (sourceTable.loc[:,\"baz\"]>1)))\nsourceTable = sourceTable[cond]\nsourceTable.loc[:,\"missing\"] = sourceTable.loc[:,\"qux\"].isna()\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn read_path_test() {
+        let input = r#"
+        sourceTable
+        | READ csv "data/equipment list.csv"
+        | WHERE OperationalHours > 10
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = pd.read_csv(\"data/equipment list.csv\") \ncond = (sourceTable.loc[:,\"OperationalHours\"]>10)\nsourceTable = sourceTable[cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new().generate(&program).unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn bound_path_test() {
+        let input = r#"
+        sourceTable
+        | READ excel
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nsourceTable = \"input.xlsx\" \nsourceTable = pd.read_excel(sourceTable) \n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .bind("sourceTable", "input.xlsx")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    /// Columns must be referenced through the variable of the table they belong to.
+    #[test]
+    fn table_variable_test() {
+        let input = r#"
+        equipment
+        | READ csv
+        | WHERE OperationalHours > 10
+        | EXTEND Hours = OperationalHours
+        "#;
+
+        let expected_output =
+            "import pandas as pd\n\nequipment = <filepath> \nequipment = pd.read_csv(equipment) \ncond = (equipment.loc[:,\"OperationalHours\"]>10)\nequipment = equipment[cond]\nequipment.loc[:,\"Hours\"] = equipment.loc[:,\"OperationalHours\"]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    /// Each READ is the only statement of its query. Pathless READs read from the bound path.
+    #[test]
+    fn read_options_test() {
+        let cases = [
+            (
+                r#"sourceTable | READ csv(sep=";", header=1, encoding="latin-1") "data.csv""#,
+                None,
+                "sourceTable = pd.read_csv(\"data.csv\", sep=\";\", header=1, encoding=\"latin-1\") \n",
+            ),
+            (
+                r#"sourceTable | READ excel(SHEET="Data")"#,
+                Some("data.xlsx"),
+                "sourceTable = \"data.xlsx\" \nsourceTable = pd.read_excel(sourceTable, sheet_name=\"Data\") \n",
+            ),
+        ];
+
+        for (input, bound_path, expected_output) in cases {
+            let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+            let mut pars = RustyParser::new(lex);
+            let program = pars.program().unwrap();
+            let mut codegen = PythonCodeGen::new();
+            if let Some(path) = bound_path {
+                codegen = codegen.bind("sourceTable", path);
+            }
+            let python_output = codegen.generate(&program).unwrap();
+            assert_eq!(
+                format!("import pandas as pd\n\n{}", expected_output),
+                python_output
+            );
+        }
+    }
 
-        fn str(&mut self) -> Result<(), ParseErr> {
-            self.match_token(&Token::QuotationMark)?;
-            self.python_output.push('"');
+    #[test]
+    fn invalid_read_options_test() {
+        let input = r#"
+        sourceTable
+        | READ excel(sep=";")
+        | READ csv(sep=";", sep=",")
+        | READ csv(header)
+        "#;
 
-            let mut current_str = String::from("");
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(3, errors.len());
+        assert!(errors[0].message().contains("sheet, header, skiprows"));
+    }
 
-            loop {
-                match self.current_token.as_ref() {
-                    Some(Token::Identity(identity)) => {
-                        current_str.push_str(identity); // Appending the token string
-                        current_str.push(' '); // Appending a whitespace character
-                        self.move_token();
-                    }
-                    Some(Token::QuotationMark) => {
-                        if !current_str.is_empty() {
-                            current_str.pop(); // Removing the trailing whitespace added during the while-let loop above.
-                        }
-                        self.python_output.push_str(&current_str);
-                        self.python_output.push('"');
-                        self.move_token();
-                        break;
-                    }
-                    Some(tok) => {
-                        return Err(ParseErr::WrongToken {
-                            expected: vec![
-                                Token::QuotationMark,
-                                Token::Identity("Identity".to_string()),
-                            ],
-                            actual: tok.clone(),
-                            source: Box::new(BaseErr {}),
-                        })
-                    }
-                    None => {
-                        return Err(ParseErr::NoTokenLeftError {
-                            source: Box::new(BaseErr {}),
-                        })
-                    }
-                }
+    /// Each READ is the only statement of its query. Pathless READs read from the bound path.
+    #[test]
+    fn read_formats_test() {
+        let cases = [
+            (
+                r#"sourceTable | READ parquet "data.parquet""#,
+                None,
+                "sourceTable = pd.read_parquet(\"data.parquet\") \n",
+            ),
+            (
+                r#"sourceTable | READ json(orient="records")"#,
+                Some("data.json"),
+                "sourceTable = \"data.json\" \nsourceTable = pd.read_json(sourceTable, orient=\"records\") \n",
+            ),
+            (
+                r#"sourceTable | READ jsonl "events.jsonl""#,
+                None,
+                "sourceTable = pd.read_json(\"events.jsonl\", lines=True) \n",
+            ),
+            (
+                r#"sourceTable | READ feather"#,
+                Some("data.feather"),
+                "sourceTable = \"data.feather\" \nsourceTable = pd.read_feather(sourceTable) \n",
+            ),
+            (
+                r#"sourceTable | READ sql("sites", conn, schema="public")"#,
+                None,
+                "sourceTable = pd.read_sql_table(\"sites\", conn, schema=\"public\") \n",
+            ),
+        ];
+
+        for (input, bound_path, expected_output) in cases {
+            let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+            let mut pars = RustyParser::new(lex);
+            let program = pars.program().unwrap();
+            let mut codegen = PythonCodeGen::new();
+            if let Some(path) = bound_path {
+                codegen = codegen.bind("sourceTable", path);
             }
-            Ok(())
+            let python_output = codegen.generate(&program).unwrap();
+            assert_eq!(
+                format!("import pandas as pd\n\n{}", expected_output),
+                python_output
+            );
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn invalid_read_formats_test() {
+        let input = r#"
+        sourceTable
+        | READ feather(sep=";")
+        | READ parquet(encoding="utf-8")
+        | READ sql("sites")
+        | READ avro
+        "#;
 
-    use super::{parser::RustyParser};
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(4, errors.len());
+        assert_eq!(
+            "This format does not take any options.",
+            errors[0].message()
+        );
+        assert!(errors[3].message().contains("jsonl, feather, sql"));
+    }
 
-    /// Extremely basic test to see if the parsing even works.
-    /// If this fails, this means that there are serious underlying problems that needs to be fixed even before addressing any other failed tests.
     #[test]
-    fn basic_parsing_test() {
+    fn write_test() {
+        let input = r#"
+        sourceTable
+        | READ csv "in.csv"
+        | EXTEND checked = true
+        | WRITE csv(sep=";") "out.csv"
+        | WRITE parquet(index=true) "out.parquet"
+        | WRITE jsonl "out.jsonl"
+        | WRITE sql("sites", conn, if_exists="replace")
+        "#;
+
+        let expected_output = "import pandas as pd\n\n\
+            sourceTable = pd.read_csv(\"in.csv\") \n\
+            sourceTable.loc[:,\"checked\"] = True\n\
+            sourceTable.to_csv(\"out.csv\", index=False, sep=\";\")\n\
+            sourceTable.to_parquet(\"out.parquet\", index=True)\n\
+            sourceTable.to_json(\"out.jsonl\", orient=\"records\", lines=True)\n\
+            sourceTable.to_sql(\"sites\", conn, index=False, if_exists=\"replace\")\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new().generate(&program).unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn invalid_write_test() {
         let input = r#"
         sourceTable
         | READ csv
-        | WHERE ["foo bar"] > 5
+        | WRITE csv
+        | WRITE feather(index=false) "out.feather"
         "#;
 
-        let expected_output =
-            "sourceTable = <filepath> \nsourceTable = pd.DataFrame.read_csv(sourceTable) \ncond = (df.loc[:,\"foo bar\"]>5)\nsourceTable = sourceTable[cond]\n";
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(2, errors.len());
+        assert_eq!(
+            "Expected the path of the file to write.",
+            errors[0].message()
+        );
+    }
+
+    #[test]
+    fn project_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | PROJECT foo, ["bar baz"], total = foo * 2
+        | PROJECT-RENAME qux = ["bar baz"], ['all'] = total
+        | PROJECT-AWAY foo
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = sourceTable.assign(**{\"total\": sourceTable.loc[:,\"foo\"]*2})\n\
+            sourceTable = sourceTable.loc[:,[\"foo\", \"bar baz\", \"total\"]]\n\
+            sourceTable = sourceTable.rename(columns={\"bar baz\": \"qux\", \"total\": \"all\"})\n\
+            sourceTable = sourceTable.drop(columns=[\"foo\"])\n";
 
         let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
         let mut pars = RustyParser::new(lex);
-        pars.program().unwrap();
-        assert_eq!(expected_output, &pars.python_output);
-        println!("{}", &pars.python_output);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
     }
 
+    /// All PROJECT expressions read the input table, so columns can be swapped.
     #[test]
-    fn isnotnull_test() {
+    fn project_swap_test() {
         let input = r#"
         sourceTable
         | READ csv
-        | WHERE isnotnull(["foo bar baz"])
+        | PROJECT a = b, b = a
         "#;
 
-        let expected_output =
-            "sourceTable = <filepath> \nsourceTable = pd.DataFrame.read_csv(sourceTable) \ncond = (df.loc[:,\"foo bar baz\"].notna())\nsourceTable = sourceTable[cond]\n";
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = sourceTable.assign(**{\"a\": sourceTable.loc[:,\"b\"], \"b\": sourceTable.loc[:,\"a\"]})\n\
+            sourceTable = sourceTable.loc[:,[\"a\", \"b\"]]\n";
 
         let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
         let mut pars = RustyParser::new(lex);
-        pars.program().unwrap();
-        assert_eq!(expected_output, &pars.python_output);
-        println!("{}", &pars.python_output);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
     }
 
     #[test]
-    fn extend_test() {
+    fn sort_test() {
         let input = r#"
         sourceTable
         | READ csv
-        | EXTEND ["foo"] = ["bar"] * 2
-        | EXTEND ["baz"] = ["qux"] * 5.1
+        | SORT BY foo
+        | ORDER BY foo asc, ["bar"] DESC nulls first, last
         "#;
 
-        let expected_output =
-            "sourceTable = <filepath> \nsourceTable = pd.DataFrame.read_csv(sourceTable) \ndf.loc[:,\"foo\"] = df.loc[:,\"bar\"]*2\ndf.loc[:,\"baz\"] = df.loc[:,\"qux\"]*5.1\n";
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = sourceTable.sort_values(by=[\"foo\"], ascending=[False])\n\
+            sourceTable = sourceTable.sort_values(by=[\"foo\", \"bar\", \"last\"], ascending=[True, False, False], na_position=\"first\")\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn invalid_sort_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | SORT foo
+        | SORT BY foo nulls first, bar nulls last
+        | SORT BY foo nulls
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(3, errors.len());
+        assert_eq!(
+            "All sort columns must place nulls the same way.",
+            errors[1].message()
+        );
+    }
+
+    #[test]
+    fn take_top_sample_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | TAKE 100
+        | LIMIT 50
+        | TOP 10 BY foo asc, bar
+        | SAMPLE 5 seed=42
+        | SAMPLE 5
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = sourceTable.head(100)\n\
+            sourceTable = sourceTable.head(50)\n\
+            sourceTable = sourceTable.sort_values(by=[\"foo\", \"bar\"], ascending=[True, False]).head(10)\n\
+            sourceTable = sourceTable.sample(n=min(5, len(sourceTable)), random_state=42)\n\
+            sourceTable = sourceTable.sample(n=min(5, len(sourceTable)))\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn summarize_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | SUMMARIZE total = sum(Hours), n = count(), avg(Hours * 2), dcount(Site), countif(Hours > 5), percentile(Hours, 95) BY Site, Manufacturer
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable.loc[:,\"__summarize_1\"] = 1\n\
+            sourceTable.loc[:,\"__summarize_2\"] = sourceTable.loc[:,\"Hours\"]*2\n\
+            sourceTable.loc[:,\"__summarize_4\"] = sourceTable.loc[:,\"Hours\"]>5\n\
+            sourceTable = sourceTable.groupby([\"Site\", \"Manufacturer\"], dropna=False).agg(**{\
+            \"total\": (\"Hours\", \"sum\"), \
+            \"n\": (\"__summarize_1\", \"size\"), \
+            \"avg_\": (\"__summarize_2\", \"mean\"), \
+            \"dcount_Site\": (\"Site\", \"nunique\"), \
+            \"countif_\": (\"__summarize_4\", \"sum\"), \
+            \"percentile_Hours_95\": (\"Hours\", lambda s: s.quantile(0.95))\
+            }).reset_index()\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn summarize_without_by_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | SUMMARIZE n = count(), max(Hours), countif(Hours > 5), percentile(Hours * 2, 50)
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = pd.DataFrame({\
+            \"n\": [len(sourceTable)], \
+            \"max_Hours\": [sourceTable.loc[:,\"Hours\"].max()], \
+            \"countif_\": [(sourceTable.loc[:,\"Hours\"]>5).sum()], \
+            \"percentile__50\": [(sourceTable.loc[:,\"Hours\"]*2).quantile(0.5)]\
+            })\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn percentile_precision_test() {
+        let input = r#"
+        sourceTable
+        | READ csv "data.csv"
+        | SUMMARIZE low = percentile(Hours, 0.7), high = percentile(Hours, 90.1) BY Site
+        | SUMMARIZE percentile(low, 0.7)
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = pd.read_csv(\"data.csv\") \n\
+            sourceTable = sourceTable.groupby([\"Site\"], dropna=False).agg(**{\
+            \"low\": (\"Hours\", lambda s: s.quantile(0.007)), \
+            \"high\": (\"Hours\", lambda s: s.quantile(0.901))\
+            }).reset_index()\n\
+            sourceTable = pd.DataFrame({\"percentile_low_0_7\": [sourceTable.loc[:,\"low\"].quantile(0.007)]})\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new().generate(&program).unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn summarize_names_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | SUMMARIZE avg(a * 2), avg(b * 2), avg_1 = max(a), sum(a), sum(a) BY k
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let names: Vec<&str> = match &program.statements[1] {
+            Statement::Summarize { aggregations, .. } => {
+                aggregations.iter().map(|a| a.name.name.as_str()).collect()
+            }
+            _ => panic!("expected a SUMMARIZE statement"),
+        };
+        assert_eq!(vec!["avg_", "avg_2", "avg_1", "sum_a", "sum_a1"], names);
+
+        let input = r#"
+        sourceTable
+        | READ csv
+        | SUMMARIZE count(), sum(a) BY count_, sum_a
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let names: Vec<&str> = match &program.statements[1] {
+            Statement::Summarize { aggregations, .. } => {
+                aggregations.iter().map(|a| a.name.name.as_str()).collect()
+            }
+            _ => panic!("expected a SUMMARIZE statement"),
+        };
+        assert_eq!(vec!["count_1", "sum_a1"], names);
+    }
+
+    #[test]
+    fn invalid_summarize_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | SUMMARIZE total = mode(Hours)
+        | SUMMARIZE n = count(), n = sum(Hours)
+        | SUMMARIZE percentile(Hours, 150)
+        | SUMMARIZE Site = count() BY Site
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(4, errors.len());
+        assert_eq!(
+            "Column 'n' is aggregated more than once.",
+            errors[1].message()
+        );
+        assert_eq!(
+            "Column 'Site' is both aggregated and grouped by.",
+            errors[3].message()
+        );
+        assert_eq!(21, errors[3].location().column);
+    }
+
+    #[test]
+    fn distinct_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | DISTINCT Site, ["Sensor id"]
+        | DISTINCT *
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = sourceTable.drop_duplicates(subset=[\"Site\", \"Sensor id\"]).loc[:,[\"Site\", \"Sensor id\"]]\n\
+            sourceTable = sourceTable.drop_duplicates()\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn count_getschema_test() {
+        let input = r#"
+        sourceTable
+        | READ csv
+        | GETSCHEMA
+        | COUNT
+        "#;
+
+        let expected_output = "import pandas as pd\n\nsourceTable = <filepath> \n\
+            sourceTable = pd.read_csv(sourceTable) \n\
+            sourceTable = pd.DataFrame({\"ColumnName\": list(sourceTable.columns), \"ColumnOrdinal\": range(len(sourceTable.columns)), \"DataType\": sourceTable.dtypes.astype(str).tolist()})\n\
+            sourceTable = pd.DataFrame({\"Count\": [len(sourceTable)]})\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn join_test() {
+        let input = r#"
+        equipment
+        | READ csv "equipment.csv"
+        | JOIN kind=leftouter (sites | READ csv "sites.csv" | WHERE Active == 1) ON SiteId
+        | JOIN (owners | READ excel) ON $right.Id == $left.OwnerId, Region
+        "#;
+
+        let expected_output = "import pandas as pd\n\n\
+            equipment = pd.read_csv(\"equipment.csv\") \n\
+            sites = pd.read_csv(\"sites.csv\") \n\
+            cond = (sites.loc[:,\"Active\"]==1)\n\
+            sites = sites[cond]\n\
+            equipment = equipment.merge(sites, how=\"left\", on=[\"SiteId\"], suffixes=(\"\", \"1\"))\n\
+            owners = <filepath> \n\
+            owners = pd.read_excel(owners) \n\
+            equipment = equipment.merge(owners, how=\"inner\", left_on=[\"OwnerId\", \"Region\"], right_on=[\"Id\", \"Region\"], suffixes=(\"\", \"1\"))\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn semi_anti_join_test() {
+        let input = r#"
+        equipment
+        | READ csv
+        | JOIN kind=leftsemi (sites | READ csv) ON SiteId
+        | JOIN kind=leftanti (retired | READ csv) ON $left.Id == $right.EquipmentId
+        "#;
+
+        let expected_output = "import pandas as pd\n\n\
+            equipment = <filepath> \n\
+            equipment = pd.read_csv(equipment) \n\
+            sites = <filepath> \n\
+            sites = pd.read_csv(sites) \n\
+            cond = equipment.set_index([\"SiteId\"]).index.isin(sites.set_index([\"SiteId\"]).index)\n\
+            equipment = equipment[cond]\n\
+            retired = <filepath> \n\
+            retired = pd.read_csv(retired) \n\
+            cond = equipment.set_index([\"Id\"]).index.isin(retired.set_index([\"EquipmentId\"]).index)\n\
+            equipment = equipment[~cond]\n";
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let program = pars.program().unwrap();
+        let python_output = PythonCodeGen::new()
+            .placeholder("<filepath>")
+            .generate(&program)
+            .unwrap();
+        assert_eq!(expected_output, &python_output);
+    }
+
+    #[test]
+    fn invalid_join_test() {
+        let input = r#"
+        equipment
+        | READ csv
+        | JOIN kind=cross (sites | READ csv) ON SiteId
+        | JOIN (equipment | READ csv) ON Id
+        | JOIN (owners | READ csv) ON $left.Id == $left.OwnerId
+        | JOIN (parts | WHERE Weight > 1) ON PartId
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(4, errors.len());
+        assert_eq!(
+            "Table 'equipment' is already used in this query.",
+            errors[1].message()
+        );
+        assert_eq!(
+            "Expected the query to start with '| READ'.",
+            errors[3].message()
+        );
+    }
+
+    #[test]
+    fn pathless_read_test() {
+        let input = r#"
+        sourceTable
+        | READ csv "data.csv"
+        | WHERE foo > 1
+        | READ json
+        "#;
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
+        let mut pars = RustyParser::new(lex);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(5, errors[0].location().line);
+        assert_eq!(
+            "Only the first READ of a query may omit the path.",
+            errors[0].message()
+        );
+    }
+
+    #[test]
+    fn missing_read_test() {
+        let input = r#"
+        sourceTable
+        | WHERE a > 1
+        "#;
 
         let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer(input);
         let mut pars = RustyParser::new(lex);
-        pars.program().unwrap();
-        assert_eq!(expected_output, &pars.python_output);
-        println!("{}", &pars.python_output);
+        let errors = pars.program().unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(3, errors[0].location().line);
+        assert_eq!(
+            "Expected the query to start with '| READ'.",
+            errors[0].message()
+        );
+
+        let lex = <crate::lexer::lexer::Token as logos::Logos>::lexer("sourceTable");
+        let mut pars = RustyParser::new(lex);
+        assert_eq!(1, pars.program().unwrap_err().len());
     }
 }